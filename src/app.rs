@@ -2,14 +2,35 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 //! Application state and logic.
 
+use anyhow::{bail, Result};
 use chrono::{DateTime, Utc};
+use ratatui::widgets::TableState;
 use runtara_management_sdk::{
-    Checkpoint, CheckpointSummary, GetTenantMetricsOptions, HealthStatus, ImageSummary,
-    InstanceInfo, InstanceStatus, InstanceSummary, ListCheckpointsOptions, ListImagesOptions,
-    ListInstancesOptions, ManagementSdk, MetricsGranularity, SdkConfig, TenantMetricsResult,
+    Checkpoint, CheckpointSummary, ImageSummary, InstanceInfo, InstanceStatus, InstanceSummary,
+    MetricsBucket, MetricsGranularity, TenantMetricsResult,
 };
-use std::net::SocketAddr;
+use std::cell::Cell;
+use std::collections::{BTreeSet, HashSet};
 use std::time::{Duration, Instant};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::clipboard;
+use crate::config::Settings;
+use crate::connection::ConnectionState;
+use crate::diff::CheckpointDiff;
+use crate::export;
+use crate::filter::InstanceFilter;
+use crate::fuzzy;
+use crate::image_metrics::ImageMetrics;
+use crate::input::TextInput;
+use crate::json_tree::JsonTree;
+use crate::notify;
+use crate::query::Query;
+use crate::range::MetricsRange;
+use crate::server::Server;
+use crate::sort::{self, CheckpointColumn, ImageColumn, InstanceColumn, MetricsColumn, Sort};
+use crate::timeline::{EventKind, Timeline};
+use crate::worker::{RefreshParams, Request, ServerUpdate, Update};
 
 /// Status filter for instances list.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -17,6 +38,8 @@ pub enum StatusFilter {
     #[default]
     All,
     Running,
+    /// Running instances whose heartbeat is stale
+    Stuck,
     Completed,
     Failed,
     Pending,
@@ -28,6 +51,7 @@ impl StatusFilter {
         match self {
             StatusFilter::All => "All",
             StatusFilter::Running => "Running",
+            StatusFilter::Stuck => "Stuck",
             StatusFilter::Completed => "Completed",
             StatusFilter::Failed => "Failed",
             StatusFilter::Pending => "Pending",
@@ -35,10 +59,10 @@ impl StatusFilter {
         }
     }
 
-    pub fn to_instance_status(&self) -> Option<InstanceStatus> {
+    pub fn to_instance_status(self) -> Option<InstanceStatus> {
         match self {
             StatusFilter::All => None,
-            StatusFilter::Running => Some(InstanceStatus::Running),
+            StatusFilter::Running | StatusFilter::Stuck => Some(InstanceStatus::Running),
             StatusFilter::Completed => Some(InstanceStatus::Completed),
             StatusFilter::Failed => Some(InstanceStatus::Failed),
             StatusFilter::Pending => Some(InstanceStatus::Pending),
@@ -49,7 +73,8 @@ impl StatusFilter {
     pub fn next(&self) -> Self {
         match self {
             StatusFilter::All => StatusFilter::Running,
-            StatusFilter::Running => StatusFilter::Completed,
+            StatusFilter::Running => StatusFilter::Stuck,
+            StatusFilter::Stuck => StatusFilter::Completed,
             StatusFilter::Completed => StatusFilter::Failed,
             StatusFilter::Failed => StatusFilter::Pending,
             StatusFilter::Pending => StatusFilter::Suspended,
@@ -95,16 +120,345 @@ pub enum ViewMode {
     CheckpointsList,
     /// Checkpoint detail view (JSON data)
     CheckpointDetail,
+    /// Diff of two marked checkpoints
+    CheckpointDiff,
+    /// Timeline of the open instance
+    Timeline,
+    /// Metrics of the image selected in the Images tab
+    ImageMetrics,
+}
+
+/// What an open text prompt is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptKind {
+    /// Free-text instance search (`/`)
+    Search,
+    /// Structured instance filters (`F`)
+    Filter,
+    /// JSON query in a detail view (`/`)
+    Query,
+    /// File to export the payload of a detail view to (`e`)
+    Export,
+    /// File or directory to export an instance with its checkpoints to (`E`)
+    ExportBundle,
+    /// Custom metrics time range (`t`)
+    MetricsRange,
+    /// Tenant the lists and metrics are scoped to (`T`)
+    Tenant,
+}
+
+/// Part of the open detail view written by an export (`Tab` in the prompt).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportPart {
+    /// The instance with its input, output and error, or the checkpoint data
+    All,
+    Input,
+    Output,
+    Error,
+    /// The tree node under the cursor
+    Selected,
+}
+
+impl ExportPart {
+    const ALL: [ExportPart; 5] = [
+        ExportPart::All,
+        ExportPart::Input,
+        ExportPart::Output,
+        ExportPart::Error,
+        ExportPart::Selected,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportPart::All => "all",
+            ExportPart::Input => "input",
+            ExportPart::Output => "output",
+            ExportPart::Error => "error",
+            ExportPart::Selected => "selection",
+        }
+    }
+}
+
+/// Row of the tenant selector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TenantChoice {
+    /// Clear the scope
+    All,
+    /// A tenant seen in the loaded lists
+    Known(String),
+    /// The text entered, when it is not a known tenant
+    Entered(String),
+}
+
+/// Rows of the open tenant selector and the one selected.
+#[derive(Debug, Clone, Default)]
+pub struct TenantChoices {
+    pub rows: Vec<TenantChoice>,
+    pub selected: usize,
+}
+
+/// Open tenant picker of the Metrics tab.
+#[derive(Debug, Clone)]
+pub struct TenantPicker {
+    /// Tenants seen in the loaded lists, sorted
+    pub tenants: Vec<String>,
+    pub selected: usize,
+    /// Tenants marked for comparison
+    pub marked: Vec<String>,
+}
+
+/// Text prompt shown in place of the filter line.
+#[derive(Debug, Clone)]
+pub struct Prompt {
+    pub kind: PromptKind,
+    pub input: TextInput,
+    /// Why the current text is not valid, for prompts that apply live
+    pub error: Option<String>,
+    /// Text before the prompt was opened, restored on cancel
+    initial: String,
+    /// Position in the query history while browsing it, and the text typed
+    /// before browsing started
+    history: Option<(usize, String)>,
+}
+
+/// Lifecycle action that can be applied to an instance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstanceAction {
+    /// Cancel a pending, running or suspended instance
+    Cancel,
+    /// Pause a running instance
+    Suspend,
+    /// Resume a suspended instance
+    Resume,
+    /// Start a new instance with the same image and input as a finished one
+    Rerun,
+}
+
+impl InstanceAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InstanceAction::Cancel => "Cancel",
+            InstanceAction::Suspend => "Suspend",
+            InstanceAction::Resume => "Resume",
+            InstanceAction::Rerun => "Re-run",
+        }
+    }
+
+    /// Whether the action applies to an instance in the given status
+    pub fn allowed(&self, status: InstanceStatus) -> bool {
+        match self {
+            InstanceAction::Cancel => matches!(
+                status,
+                InstanceStatus::Pending | InstanceStatus::Running | InstanceStatus::Suspended
+            ),
+            InstanceAction::Suspend => status == InstanceStatus::Running,
+            InstanceAction::Resume => status == InstanceStatus::Suspended,
+            InstanceAction::Rerun => {
+                matches!(status, InstanceStatus::Failed | InstanceStatus::Cancelled)
+            }
+        }
+    }
+}
+
+/// Action waiting for the user's confirmation.
+#[derive(Debug, Clone)]
+pub struct PendingAction {
+    /// Index of the server the instance runs on
+    pub server: usize,
+    pub action: InstanceAction,
+    pub instance_id: String,
+}
+
+/// Short-lived message shown in the footer.
+#[derive(Debug, Clone)]
+pub struct Flash {
+    pub text: String,
+    pub is_error: bool,
+    pub shown_at: Instant,
+}
+
+/// Something that happened to a followed instance.
+#[derive(Debug, Clone)]
+pub enum FollowEvent {
+    Status {
+        from: InstanceStatus,
+        to: InstanceStatus,
+    },
+    Checkpoint {
+        id: String,
+        size: u64,
+    },
+}
+
+/// Live updates of the open instance.
+#[derive(Debug, Default)]
+pub struct Follow {
+    /// What happened since following started, oldest first
+    pub log: Vec<(DateTime<Utc>, FollowEvent)>,
+    /// Checkpoints seen so far
+    seen: HashSet<String>,
+    /// Creation time of the newest checkpoint seen
+    newest: Option<DateTime<Utc>>,
+    /// The first poll has returned; checkpoints found after it are new
+    primed: bool,
+    polled_at: Option<Instant>,
+    polling: bool,
+    /// The instance reached a terminal state; polling stopped but the log
+    /// stays until the view is left or `f` is pressed
+    pub finished: bool,
+}
+
+/// Number of rows fetched per page.
+pub const PAGE_SIZE: u32 = 100;
+
+/// Offset of the last page for a list of `total` rows
+fn last_page_offset(total: u32) -> u32 {
+    total.saturating_sub(1) / PAGE_SIZE * PAGE_SIZE
+}
+
+/// How long a flash message stays visible.
+const FLASH_DURATION: Duration = Duration::from_secs(5);
+
+/// Interval between polls of a followed instance
+const FOLLOW_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Interval between scans of the running instances, unless the Stuck filter
+/// needs them on every refresh
+const RUNNING_SCAN_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Freshness of the data shown in a view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoadState {
+    /// Data is up to date
+    #[default]
+    Fresh,
+    /// A fetch is in flight
+    Loading,
+    /// The last fetch failed; previous data is still shown
+    Stale,
+}
+
+impl LoadState {
+    /// Combined state of several sources: loading wins over stale
+    pub fn combine(states: impl IntoIterator<Item = LoadState>) -> LoadState {
+        states
+            .into_iter()
+            .fold(LoadState::Fresh, |acc, state| match (acc, state) {
+                (LoadState::Loading, _) | (_, LoadState::Loading) => LoadState::Loading,
+                (LoadState::Stale, _) | (_, LoadState::Stale) => LoadState::Stale,
+                _ => LoadState::Fresh,
+            })
+    }
+
+    /// Indicator shown next to a view title, if any
+    pub fn label(&self) -> Option<&'static str> {
+        match self {
+            LoadState::Fresh => None,
+            LoadState::Loading => Some("loading…"),
+            LoadState::Stale => Some("stale"),
+        }
+    }
+}
+
+/// Scroll position of a table between frames.
+///
+/// Drawing builds a [`TableState`] from it, then stores where ratatui
+/// scrolled to and how many rows fit, which page keys move by.
+#[derive(Debug, Default)]
+pub struct Viewport {
+    offset: Cell<usize>,
+    height: Cell<usize>,
+}
+
+impl Viewport {
+    /// Table state for drawing with the cursor on `selected`
+    pub fn state(&self, selected: usize) -> TableState {
+        TableState::default()
+            .with_offset(self.offset.get())
+            .with_selected(Some(selected))
+    }
+
+    /// Keep `state` after drawing a table with `height` rows of data
+    pub fn save(&self, state: &TableState, height: usize) {
+        self.offset.set(state.offset());
+        self.height.set(height);
+    }
+
+    /// Rows a page key moves by
+    pub fn page(&self) -> usize {
+        self.height.get().saturating_sub(1).max(1)
+    }
+
+    /// First of `len` rows to show in a view `height` rows high, for views
+    /// drawn without a table; scrolls as little as possible to keep
+    /// `selected` in view
+    pub fn scroll(&self, selected: usize, len: usize, height: usize) -> usize {
+        let offset = keep_in_view(
+            self.offset.get().min(len.saturating_sub(1)),
+            selected,
+            height,
+        );
+        self.offset.set(offset);
+        self.height.set(height);
+        offset
+    }
+
+    /// Scroll back to the first row
+    pub fn reset(&self) {
+        self.offset.set(0);
+    }
+}
+
+/// First row to show so that row `selected` is within `height` rows of
+/// `offset`, scrolling as little as possible
+fn keep_in_view(offset: usize, selected: usize, height: usize) -> usize {
+    if selected < offset {
+        selected
+    } else if height > 0 && selected >= offset + height {
+        selected + 1 - height
+    } else {
+        offset
+    }
+}
+
+/// Cursor move by a page or to one end of a list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jump {
+    PageDown,
+    PageUp,
+    First,
+    Last,
 }
 
 /// Application state.
 pub struct App {
-    /// SDK configuration
-    pub server_addr: SocketAddr,
-    skip_cert_verification: bool,
-
-    /// Optional tenant filter
+    /// Monitored servers
+    pub servers: Vec<Server>,
+
+    /// Server the list views are limited to; `None` shows all of them
+    pub focus: Option<usize>,
+
+    /// Active configuration profile
+    pub profile: Option<String>,
+    /// Configured profile names
+    pub profiles: Vec<String>,
+    /// Selected row of the open profile switcher
+    pub profile_picker: Option<usize>,
+    /// Selected row of the open metrics range picker; the row after the
+    /// presets enters a custom range
+    pub range_picker: Option<usize>,
+    pub tenant_picker: Option<TenantPicker>,
+    /// Profile the user asked to switch to; the event loop rebuilds the app
+    pub requested_profile: Option<String>,
+    /// Profile whose app is being set up to replace this one
+    pub switching_profile: Option<String>,
+
+    /// Tenant the lists and metrics are scoped to; `None` shows all
     pub tenant_id: Option<String>,
+    /// Rows of the open tenant selector
+    pub tenant_choices: TenantChoices,
+    /// Tenants seen in any list since startup, offered by the tenant pickers
+    seen_tenants: BTreeSet<String>,
 
     /// Current tab
     pub tab: Tab,
@@ -115,293 +469,1700 @@ pub struct App {
     /// Status filter for instances
     pub status_filter: StatusFilter,
 
-    /// Health status
-    pub health: Option<HealthStatus>,
+    /// Search text and structured filters for instances
+    pub instance_filter: InstanceFilter,
+
+    /// Open text prompt, if any
+    pub prompt: Option<Prompt>,
 
-    /// List of instances
-    pub instances: Vec<InstanceSummary>,
-    pub instances_total: u32,
+    /// Whether `g` was just pressed, so a second one goes to the top
+    pub pending_g: bool,
+    /// Part written by the open export prompt
+    pub export_part: ExportPart,
+
+    /// Sort of each table, kept across refreshes
+    pub instance_sort: Sort<InstanceColumn>,
+    pub image_sort: Sort<ImageColumn>,
+    pub checkpoint_sort: Sort<CheckpointColumn>,
+    pub metrics_sort: Sort<MetricsColumn>,
+
+    /// Instances list page and selection; the page applies to every server
+    pub instances_offset: u32,
     pub instances_selected: usize,
+    pub instances_view: Viewport,
 
-    /// List of images
-    pub images: Vec<ImageSummary>,
-    pub images_total: u32,
+    /// Images list page and selection
+    pub images_offset: u32,
     pub images_selected: usize,
+    pub images_view: Viewport,
+
+    /// Server of the open instance detail and its checkpoints
+    pub detail_server: usize,
 
     /// Instance detail view
     pub instance_detail: Option<InstanceInfo>,
+    /// Tree of the instance input and output
+    pub instance_tree: Option<JsonTree>,
+    /// Live updates of the open instance, while following it
+    pub follow: Option<Follow>,
 
     /// Checkpoints list for current instance
     pub checkpoints: Vec<CheckpointSummary>,
     pub checkpoints_total: u32,
+    pub checkpoints_offset: u32,
     pub checkpoints_selected: usize,
+    pub checkpoints_view: Viewport,
+    pub checkpoints_state: LoadState,
+    /// Checkpoints marked for diffing, at most two
+    pub checkpoints_marked: Vec<CheckpointSummary>,
 
     /// Checkpoint detail view
     pub checkpoint_detail: Option<Checkpoint>,
+    /// Tree of the checkpoint data
+    pub checkpoint_tree: Option<JsonTree>,
+
+    /// Checkpoint diff view
+    pub checkpoint_diff: Option<CheckpointDiff>,
+
+    /// Timeline of the open instance, once its checkpoints are loaded
+    pub timeline: Option<Timeline>,
+
+    /// Image whose metrics are open
+    pub metrics_image: Option<ImageSummary>,
+    /// Its metrics, once its instances are loaded
+    pub image_metrics: Option<ImageMetrics>,
+    /// Bucket width of the image metrics; starts at the Metrics tab's
+    image_metrics_granularity: MetricsGranularity,
+    pub image_metrics_selected: usize,
+    pub image_metrics_view: Viewport,
+
+    /// JSON queries entered this session, oldest first
+    pub query_history: Vec<String>,
 
-    /// Metrics data
-    pub metrics: Option<TenantMetricsResult>,
+    /// Metrics settings; the data is kept per server
     pub metrics_granularity: MetricsGranularity,
+    pub metrics_range: MetricsRange,
+    /// Tenant picked on the Metrics tab, shown instead of `tenant_id`
+    pub metrics_tenant: Option<String>,
+    /// Tenants compared side by side; empty when not comparing
+    pub compare_tenants: Vec<String>,
     pub metrics_selected: usize,
-
-    /// Scroll offset for detail views
-    pub detail_scroll: u16,
+    pub metrics_view: Viewport,
 
     /// Last refresh time
     pub last_refresh: Option<Instant>,
+    /// Time of the last scan of the running instances
+    running_scanned: Option<Instant>,
     pub refresh_interval: Duration,
+    /// Heartbeat age after which a running instance is stale
+    pub stale_after: Duration,
+
+    /// Whether another refresh was requested while one was in flight
+    refresh_queued: bool,
+    /// Tag of the refresh whose lists and metrics are current
+    refresh_seq: u64,
+
+    /// Sequence number of the current drill-down view; bumped whenever the
+    /// view changes so late results for a view already left are dropped
+    view_seq: u64,
 
     /// Error message (if any)
     pub error: Option<String>,
 
-    /// Connection status
-    pub connected: bool,
+    /// Action awaiting confirmation
+    pub pending_action: Option<PendingAction>,
+
+    /// Result message for the last action
+    pub flash: Option<Flash>,
 }
 
 impl App {
-    pub fn new(
-        server: &str,
-        skip_cert_verification: bool,
-        tenant_id: Option<String>,
-        refresh_interval: Duration,
-    ) -> Self {
-        let server_addr: SocketAddr = server
-            .parse()
-            .unwrap_or_else(|_| "127.0.0.1:8002".parse().unwrap());
-
-        Self {
-            server_addr,
-            skip_cert_verification,
-            tenant_id,
+    /// Create the app and start connecting to every server.
+    ///
+    /// The first settings provide the tenant and refresh interval. A server
+    /// whose address is invalid or does not resolve is kept in a failed
+    /// state; fails only when no server can be set up.
+    pub async fn new(
+        settings: &[Settings],
+        profiles: Vec<String>,
+        updates: UnboundedSender<ServerUpdate>,
+    ) -> Result<Self> {
+        let first = &settings[0];
+
+        // Label servers by profile when each has its own, else by address
+        let names: Vec<String> = settings.iter().filter_map(|s| s.profile.clone()).collect();
+        let distinct: HashSet<&String> = names.iter().collect();
+        let by_profile = names.len() == settings.len() && distinct.len() == names.len();
+        let profile = if by_profile {
+            Some(names.join(", "))
+        } else {
+            first.profile.clone()
+        };
+
+        let results = futures::future::join_all(settings.iter().enumerate().map(|(i, s)| {
+            let name = match s.profile {
+                Some(ref profile) if by_profile => profile.clone(),
+                _ => s.server.clone(),
+            };
+            let updates = updates.clone();
+            async move {
+                let result = Server::connect(i, name.clone(), s, updates).await;
+                (name, result)
+            }
+        }))
+        .await;
+
+        // Keep servers that failed to set up so they show their error, as
+        // long as at least one is usable
+        if results.iter().all(|(_, result)| result.is_err()) {
+            let mut errors: Vec<String> = Vec::new();
+            for (name, result) in results {
+                let Err(e) = result else { continue };
+                if settings.len() == 1 {
+                    return Err(e);
+                }
+                errors.push(format!("{}: {:#}", name, e));
+            }
+            bail!("No server could be set up:\n{}", errors.join("\n"));
+        }
+        let servers = results
+            .into_iter()
+            .zip(settings)
+            .map(|((name, result), s)| {
+                result.unwrap_or_else(|e| Server::failed(name, s, format!("{:#}", e)))
+            })
+            .collect();
+
+        Ok(Self {
+            servers,
+            focus: None,
+            profile,
+            profiles,
+            profile_picker: None,
+            range_picker: None,
+            tenant_picker: None,
+            requested_profile: None,
+            switching_profile: None,
+            tenant_id: first.tenant.clone(),
+            tenant_choices: TenantChoices::default(),
+            seen_tenants: BTreeSet::new(),
             tab: Tab::Instances,
             view_mode: ViewMode::List,
             status_filter: StatusFilter::All,
-            health: None,
-            instances: Vec::new(),
-            instances_total: 0,
+            instance_filter: InstanceFilter::default(),
+            prompt: None,
+            pending_g: false,
+            export_part: ExportPart::All,
+            instance_sort: Sort::default(),
+            image_sort: Sort::default(),
+            checkpoint_sort: Sort::default(),
+            metrics_sort: Sort::default(),
+            instances_offset: 0,
             instances_selected: 0,
-            images: Vec::new(),
-            images_total: 0,
+            instances_view: Viewport::default(),
+            images_offset: 0,
             images_selected: 0,
+            images_view: Viewport::default(),
+            detail_server: 0,
             instance_detail: None,
+            instance_tree: None,
             checkpoints: Vec::new(),
             checkpoints_total: 0,
+            checkpoints_offset: 0,
             checkpoints_selected: 0,
+            checkpoints_view: Viewport::default(),
+            checkpoints_state: LoadState::Fresh,
+            checkpoints_marked: Vec::new(),
             checkpoint_detail: None,
-            metrics: None,
+            checkpoint_tree: None,
+            checkpoint_diff: None,
+            timeline: None,
+            metrics_image: None,
+            image_metrics: None,
+            image_metrics_granularity: MetricsGranularity::Hourly,
+            image_metrics_selected: 0,
+            image_metrics_view: Viewport::default(),
+            query_history: Vec::new(),
             metrics_granularity: MetricsGranularity::Hourly,
+            metrics_range: MetricsRange::default(),
+            metrics_tenant: None,
+            compare_tenants: Vec::new(),
             metrics_selected: 0,
-            detail_scroll: 0,
+            metrics_view: Viewport::default(),
             last_refresh: None,
-            refresh_interval,
+            running_scanned: None,
+            refresh_interval: first.refresh_interval,
+            stale_after: first.stale_after,
+            refresh_queued: false,
+            refresh_seq: 0,
+            view_seq: 0,
             error: None,
-            connected: false,
+            pending_action: None,
+            follow: None,
+            flash: None,
+        })
+    }
+
+    /// Start a background refresh of all data on every server
+    pub fn refresh(&mut self) {
+        if self.refreshing() {
+            // What is asked for changed; drop the results still in flight
+            self.refresh_seq += 1;
+            self.refresh_queued = true;
+            return;
+        }
+
+        self.error = None;
+        let scan_running = self.status_filter == StatusFilter::Stuck
+            || match self.running_scanned {
+                Some(scanned) => scanned.elapsed() >= RUNNING_SCAN_INTERVAL,
+                None => true,
+            };
+        if scan_running {
+            self.running_scanned = Some(Instant::now());
+        }
+        let params = RefreshParams {
+            tenant_id: self.tenant_id.clone(),
+            metrics_tenant: self.metrics_tenant().map(str::to_string),
+            compare_tenants: self.compare_tenants.clone(),
+            status: self.status_filter.to_instance_status(),
+            instance_filter: self.instance_filter.clone(),
+            instance_order: self.instance_sort.server_order(),
+            instances_offset: self.instances_offset,
+            images_offset: self.images_offset,
+            granularity: self.metrics_granularity,
+            metrics_range: self.metrics_range,
+            scan_running,
+            seq: self.refresh_seq,
+        };
+        for server in &mut self.servers {
+            server.start_refresh();
+            server.send(Request::Refresh(params.clone()));
         }
     }
 
-    /// Create SDK instance
-    fn create_sdk(&self) -> Result<ManagementSdk, runtara_management_sdk::SdkError> {
-        let config = SdkConfig {
-            server_addr: self.server_addr,
-            server_name: "localhost".to_string(),
-            skip_cert_verification: self.skip_cert_verification,
-            connect_timeout: Duration::from_secs(5),
-            request_timeout: Duration::from_secs(10),
+    /// Whether a refresh is in flight on any server
+    pub fn refreshing(&self) -> bool {
+        self.servers.iter().any(|s| s.refreshing)
+    }
+
+    /// Check if we should auto-refresh
+    pub fn should_refresh(&self) -> bool {
+        if self.refreshing() {
+            return false;
+        }
+        match self.last_refresh {
+            Some(last) => last.elapsed() >= self.refresh_interval,
+            None => true,
+        }
+    }
+
+    /// Report an error from one server.
+    ///
+    /// With a single server it is shown as a popup; with several it is kept
+    /// on the server so one failing server does not interrupt the others.
+    fn report_error(&mut self, index: usize, error: String) {
+        if self.servers.len() == 1 {
+            self.error = Some(error.clone());
+        }
+        self.servers[index].error = Some(error);
+    }
+
+    /// Apply a result reported by the background worker of a server
+    pub fn apply(&mut self, update: ServerUpdate) {
+        let ServerUpdate {
+            server: index,
+            update,
+        } = update;
+        if index >= self.servers.len() {
+            return;
+        }
+
+        match update {
+            Update::Connection(state) => {
+                // Report the first failure only; retries are shown in the header
+                if let ConnectionState::Waiting {
+                    attempt: 1,
+                    ref error,
+                    ..
+                } = state
+                {
+                    self.report_error(index, format!("Connection failed: {}", error));
+                }
+                self.servers[index].connection = state;
+            }
+            Update::Disconnected => {
+                self.finish_refresh(index);
+            }
+            Update::Health(result) => match result {
+                Ok(health) => {
+                    let server = &mut self.servers[index];
+                    server.health = Some(health);
+                    server.health_state = LoadState::Fresh;
+                    server.error = None;
+                }
+                Err(e) => {
+                    self.report_error(index, e);
+                    self.servers[index].health_state = LoadState::Stale;
+                }
+            },
+            // Results of a refresh superseded while in flight
+            Update::Instances { seq, .. }
+            | Update::Images { seq, .. }
+            | Update::Metrics { seq, .. }
+            | Update::Comparison { seq, .. }
+                if seq != self.refresh_seq => {}
+            Update::Instances { result, .. } => match result {
+                Ok(result) => {
+                    self.seen_tenants
+                        .extend(result.instances.iter().map(|inst| inst.tenant_id.clone()));
+                    let server = &mut self.servers[index];
+                    server.instances = result.instances;
+                    server.instances_total = result.total_count;
+                    server.instances_state = LoadState::Fresh;
+                    self.clamp_instances_selection();
+                }
+                Err(e) => {
+                    self.report_error(index, e);
+                    self.servers[index].instances_state = LoadState::Stale;
+                }
+            },
+            Update::Running(result) => match result {
+                Ok(scan) => {
+                    self.seen_tenants.extend(
+                        scan.instances
+                            .iter()
+                            .map(|(inst, _)| inst.tenant_id.clone()),
+                    );
+                    let server = &mut self.servers[index];
+                    server.running = scan.instances;
+                    server.running_total = scan.total;
+                    self.clamp_instances_selection();
+                }
+                Err(e) => self.report_error(index, e),
+            },
+            Update::Images { result, .. } => match result {
+                Ok(result) => {
+                    self.seen_tenants
+                        .extend(result.images.iter().map(|img| img.tenant_id.clone()));
+                    let server = &mut self.servers[index];
+                    server.images = result.images;
+                    server.images_total = result.total_count;
+                    server.images_state = LoadState::Fresh;
+                    self.clamp_images_selection();
+                }
+                Err(e) => {
+                    self.report_error(index, e);
+                    self.servers[index].images_state = LoadState::Stale;
+                }
+            },
+            Update::Metrics { result, .. } => match result {
+                Some(Ok(result)) => {
+                    let server = &mut self.servers[index];
+                    server.metrics = Some(result);
+                    server.metrics_state = LoadState::Fresh;
+                    let bucket_count = self.metrics().map_or(0, |m| m.buckets.len());
+                    if self.metrics_selected >= bucket_count && bucket_count > 0 {
+                        self.metrics_selected = bucket_count - 1;
+                    }
+                }
+                Some(Err(e)) => {
+                    self.report_error(index, e);
+                    self.servers[index].metrics_state = LoadState::Stale;
+                }
+                None => self.servers[index].metrics_state = LoadState::Fresh,
+            },
+            Update::Comparison { result, .. } => {
+                let server = &mut self.servers[index];
+                server.comparison = result;
+                server.comparison_state = LoadState::Fresh;
+            }
+            Update::RefreshDone => {
+                self.finish_refresh(index);
+            }
+            Update::InstanceDetail { seq, result } => {
+                if seq != self.view_seq {
+                    return;
+                }
+                match result {
+                    Ok(info) => {
+                        show_tree(&mut self.instance_tree, instance_data(&info));
+                        self.instance_detail = Some(*info);
+                    }
+                    Err(e) => {
+                        self.error = Some(e);
+                        self.go_back();
+                    }
+                }
+            }
+            Update::Checkpoints { seq, result } => {
+                if seq != self.view_seq {
+                    return;
+                }
+                match result {
+                    Ok(result) => {
+                        if result.offset != self.checkpoints_offset {
+                            return;
+                        }
+                        self.checkpoints = result.checkpoints;
+                        self.checkpoints_total = result.total_count;
+                        self.checkpoints_selected = 0;
+                        self.checkpoints_state = LoadState::Fresh;
+                    }
+                    Err(e) => {
+                        self.error = Some(e);
+                        if self.checkpoints.is_empty() {
+                            self.go_back();
+                        } else {
+                            self.checkpoints_state = LoadState::Stale;
+                        }
+                    }
+                }
+            }
+            Update::ActionDone {
+                action,
+                instance_id,
+                result,
+            } => {
+                match result {
+                    Ok(message) => self.show_flash(message, false),
+                    Err(e) => self.show_flash(
+                        format!("{} {} failed: {}", action.as_str(), instance_id, e),
+                        true,
+                    ),
+                }
+                self.refresh();
+                if self.detail_server == index
+                    && self
+                        .instance_detail
+                        .as_ref()
+                        .is_some_and(|info| info.instance_id == instance_id)
+                {
+                    self.reload_instance_detail();
+                }
+            }
+            Update::Follow { seq, result } => {
+                if let Some(follow) = self.follow.as_mut() {
+                    follow.polling = false;
+                }
+                if seq != self.view_seq {
+                    return;
+                }
+                match result {
+                    Ok((info, checkpoints)) => self.follow_update(*info, checkpoints),
+                    // Keep following; the next poll may succeed
+                    Err(e) => self.show_flash(e, true),
+                }
+            }
+            Update::Timeline { seq, result } => {
+                if seq != self.view_seq {
+                    return;
+                }
+                match (result, &self.instance_detail) {
+                    (Ok(checkpoints), Some(info)) => {
+                        let stale_after =
+                            chrono::Duration::from_std(self.stale_after).unwrap_or_default();
+                        self.timeline =
+                            Some(Timeline::new(info, &checkpoints, Utc::now(), stale_after));
+                    }
+                    (Ok(_), None) => {}
+                    (Err(e), _) => {
+                        self.error = Some(e);
+                        self.go_back();
+                    }
+                }
+            }
+            Update::ImageInstances { seq, result } => {
+                if seq != self.view_seq {
+                    return;
+                }
+                match result {
+                    Ok(listed) => {
+                        self.image_metrics =
+                            Some(ImageMetrics::new(listed, self.image_metrics_granularity));
+                        self.image_metrics_selected = 0;
+                    }
+                    Err(e) => {
+                        self.error = Some(e);
+                        self.go_back();
+                    }
+                }
+            }
+            Update::ExportDone(result) => match result {
+                Ok(message) => self.show_flash(message, false),
+                Err(e) => self.show_flash(e, true),
+            },
+            Update::CheckpointDetail { seq, result } => {
+                if seq != self.view_seq {
+                    return;
+                }
+                match result {
+                    Ok(checkpoint) if self.view_mode == ViewMode::CheckpointDiff => {
+                        if let Some(diff) = self.checkpoint_diff.as_mut() {
+                            diff.receive(checkpoint);
+                        }
+                    }
+                    Ok(checkpoint) => {
+                        show_tree(&mut self.checkpoint_tree, checkpoint.data.clone());
+                        self.checkpoint_detail = Some(checkpoint);
+                    }
+                    Err(e) => {
+                        self.error = Some(e);
+                        self.go_back();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Mark the in-flight refresh of one server as finished
+    fn finish_refresh(&mut self, index: usize) {
+        self.servers[index].finish_refresh();
+        if self.refreshing() {
+            return;
+        }
+        self.last_refresh = Some(Instant::now());
+
+        // The lists shrank below the current page; jump to the last one
+        let instances_total = self.servers.iter().map(|s| s.instances_total).max();
+        if let Some(total) = instances_total {
+            if self.instances_offset > 0 && self.instances_offset >= total {
+                self.instances_offset = last_page_offset(total);
+                self.refresh_queued = true;
+            }
+        }
+        let images_total = self.servers.iter().map(|s| s.images_total).max();
+        if let Some(total) = images_total {
+            if self.images_offset > 0 && self.images_offset >= total {
+                self.images_offset = last_page_offset(total);
+                self.refresh_queued = true;
+            }
+        }
+
+        if self.refresh_queued {
+            self.refresh_queued = false;
+            self.refresh();
+        }
+    }
+
+    /// Show a message in the footer for a few seconds
+    pub fn show_flash(&mut self, text: impl Into<String>, is_error: bool) {
+        self.flash = Some(Flash {
+            text: text.into(),
+            is_error,
+            shown_at: Instant::now(),
+        });
+    }
+
+    /// Current flash message, if it has not expired
+    pub fn active_flash(&self) -> Option<&Flash> {
+        self.flash
+            .as_ref()
+            .filter(|flash| flash.shown_at.elapsed() < FLASH_DURATION)
+    }
+
+    /// Ask for confirmation of an action on the instance in focus.
+    ///
+    /// Uses the open instance detail, or the selected row in the list.
+    pub fn request_action(&mut self, action: InstanceAction) {
+        let target = match (&self.instance_detail, self.view_mode) {
+            (Some(info), ViewMode::InstanceDetail) => {
+                Some((self.detail_server, info.instance_id.clone(), info.status))
+            }
+            (_, ViewMode::List) if self.tab == Tab::Instances => self
+                .selected_instance()
+                .map(|(server, inst)| (server, inst.instance_id.clone(), inst.status)),
+            _ => None,
         };
-        ManagementSdk::new(config)
+        let Some((server, instance_id, status)) = target else {
+            return;
+        };
+
+        if !action.allowed(status) {
+            let (status_text, _) = status_style(status);
+            self.show_flash(
+                format!(
+                    "Cannot {} a {} instance",
+                    action.as_str().to_lowercase(),
+                    status_text
+                ),
+                true,
+            );
+            return;
+        }
+
+        self.pending_action = Some(PendingAction {
+            server,
+            action,
+            instance_id,
+        });
     }
 
-    /// Refresh all data from server
-    pub async fn refresh(&mut self) {
+    /// Run the action awaiting confirmation
+    pub fn confirm_action(&mut self) {
+        if let Some(pending) = self.pending_action.take() {
+            self.show_flash(
+                format!("{} {}…", pending.action.as_str(), pending.instance_id),
+                false,
+            );
+            self.servers[pending.server].send(Request::Action {
+                action: pending.action,
+                instance_id: pending.instance_id,
+            });
+        }
+    }
+
+    /// Drop the action awaiting confirmation
+    pub fn cancel_action(&mut self) {
+        self.pending_action = None;
+    }
+
+    /// Re-fetch the open instance detail after its state changed
+    fn reload_instance_detail(&mut self) {
+        if let Some(info) = &self.instance_detail {
+            let instance_id = info.instance_id.clone();
+            self.servers[self.detail_server].send(Request::InstanceDetail {
+                seq: self.view_seq,
+                instance_id,
+            });
+        }
+    }
+
+    /// Dismiss the error popup
+    pub fn dismiss_error(&mut self) {
         self.error = None;
+    }
 
-        let sdk = match self.create_sdk() {
-            Ok(sdk) => sdk,
-            Err(e) => {
-                self.error = Some(format!("Failed to create SDK: {}", e));
-                self.connected = false;
-                return;
+    /// Switch to next tab
+    pub fn next_tab(&mut self) {
+        self.tab = match self.tab {
+            Tab::Instances => Tab::Images,
+            Tab::Images => Tab::Metrics,
+            Tab::Metrics => Tab::Health,
+            Tab::Health => Tab::Instances,
+        };
+    }
+
+    /// Switch to previous tab
+    pub fn previous_tab(&mut self) {
+        self.tab = match self.tab {
+            Tab::Instances => Tab::Health,
+            Tab::Images => Tab::Instances,
+            Tab::Metrics => Tab::Images,
+            Tab::Health => Tab::Metrics,
+        };
+    }
+
+    /// Set tab by index
+    pub fn set_tab(&mut self, index: usize) {
+        self.tab = match index {
+            0 => Tab::Instances,
+            1 => Tab::Images,
+            2 => Tab::Metrics,
+            3 => Tab::Health,
+            _ => Tab::Instances,
+        };
+    }
+
+    /// Whether more than one server is monitored
+    pub fn multi_server(&self) -> bool {
+        self.servers.len() > 1
+    }
+
+    /// Servers shown in the list views, with their index
+    pub fn shown_servers(&self) -> impl Iterator<Item = (usize, &Server)> {
+        self.servers
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.focus.is_none() || self.focus == Some(*i))
+    }
+
+    /// Focus the next server, or all of them after the last one
+    pub fn cycle_focus(&mut self) {
+        if !self.multi_server() {
+            return;
+        }
+        self.focus = match self.focus {
+            None => Some(0),
+            Some(i) if i + 1 < self.servers.len() => Some(i + 1),
+            Some(_) => None,
+        };
+        self.instances_selected = 0;
+        self.images_selected = 0;
+        self.metrics_selected = 0;
+    }
+
+    /// Instances on the current page that match the search, in display
+    /// order, with the index of their server
+    pub fn visible_instances(&self) -> Vec<(usize, &InstanceSummary)> {
+        let mut rows = self.filtered_instances();
+        let now = Utc::now();
+        self.instance_sort
+            .apply(&mut rows, |column, &(_, a), &(_, b)| match column {
+                InstanceColumn::Status => (a.status as u8).cmp(&(b.status as u8)),
+                InstanceColumn::Tenant => a.tenant_id.cmp(&b.tenant_id),
+                InstanceColumn::Image => a.image_id.cmp(&b.image_id),
+                // Also ordered by the server; this merges the pages of
+                // several servers
+                InstanceColumn::Created => a.created_at.cmp(&b.created_at),
+                InstanceColumn::Finished => a.finished_at.cmp(&b.finished_at),
+                InstanceColumn::Duration => {
+                    let duration = |inst: &InstanceSummary| {
+                        inst.started_at
+                            .map(|started| inst.finished_at.unwrap_or(now) - started)
+                    };
+                    duration(a).cmp(&duration(b))
+                }
+            });
+        rows
+    }
+
+    /// Instances on the current page that match the search, in server order
+    fn filtered_instances(&self) -> Vec<(usize, &InstanceSummary)> {
+        if self.status_filter == StatusFilter::Stuck {
+            return self
+                .stuck_instances()
+                .into_iter()
+                .filter(|&(i, inst)| {
+                    let server = &self.servers[i];
+                    self.instance_filter
+                        .matches(inst, server.image_name(&inst.image_id))
+                })
+                .collect();
+        }
+        self.shown_servers()
+            .flat_map(|(i, server)| {
+                server
+                    .instances
+                    .iter()
+                    .filter(|inst| {
+                        self.instance_filter
+                            .matches(inst, server.image_name(&inst.image_id))
+                    })
+                    .map(move |inst| (i, inst))
+            })
+            .collect()
+    }
+
+    /// The selected row of the instances list
+    pub fn selected_instance(&self) -> Option<(usize, &InstanceSummary)> {
+        self.visible_instances()
+            .get(self.instances_selected)
+            .copied()
+    }
+
+    /// Total instances matching the filters on the shown servers
+    pub fn instances_total(&self) -> u32 {
+        if self.status_filter == StatusFilter::Stuck {
+            return self.stuck_instances().len() as u32;
+        }
+        self.shown_servers().map(|(_, s)| s.instances_total).sum()
+    }
+
+    /// Whether a running instance last seen at `last_seen` is stale
+    pub fn is_stale(&self, last_seen: DateTime<Utc>) -> bool {
+        (Utc::now() - last_seen)
+            .to_std()
+            .is_ok_and(|age| age > self.stale_after)
+    }
+
+    /// Stale running instances on the shown servers
+    pub fn stuck_instances(&self) -> Vec<(usize, &InstanceSummary)> {
+        self.shown_servers()
+            .flat_map(|(i, server)| {
+                server
+                    .running
+                    .iter()
+                    .filter(|(_, last_seen)| self.is_stale(*last_seen))
+                    .map(move |(inst, _)| (i, inst))
+            })
+            .collect()
+    }
+
+    /// Number of stale running instances on all servers, and whether there
+    /// may be more than were scanned
+    pub fn stuck_count(&self) -> (usize, bool) {
+        let count = self
+            .servers
+            .iter()
+            .flat_map(|server| &server.running)
+            .filter(|(_, last_seen)| self.is_stale(*last_seen))
+            .count();
+        let partial = self
+            .servers
+            .iter()
+            .any(|server| server.running_total as usize > server.running.len());
+        (count, partial)
+    }
+
+    pub fn instances_state(&self) -> LoadState {
+        LoadState::combine(self.shown_servers().map(|(_, s)| s.instances_state))
+    }
+
+    /// Images on the current page in display order, with the index of their
+    /// server
+    pub fn visible_images(&self) -> Vec<(usize, &ImageSummary)> {
+        let mut rows: Vec<_> = self
+            .shown_servers()
+            .flat_map(|(i, server)| server.images.iter().map(move |img| (i, img)))
+            .collect();
+        self.image_sort
+            .apply(&mut rows, |column, (_, a), (_, b)| match column {
+                ImageColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                ImageColumn::Runner => (a.runner_type as u8).cmp(&(b.runner_type as u8)),
+                ImageColumn::Created => a.created_at.cmp(&b.created_at),
+            });
+        rows
+    }
+
+    /// Total images on the shown servers
+    pub fn images_total(&self) -> u32 {
+        self.shown_servers().map(|(_, s)| s.images_total).sum()
+    }
+
+    pub fn images_state(&self) -> LoadState {
+        LoadState::combine(self.shown_servers().map(|(_, s)| s.images_state))
+    }
+
+    /// Server whose metrics are shown: the focused one, else the first
+    pub fn metrics_server(&self) -> &Server {
+        &self.servers[self.focus.unwrap_or(0)]
+    }
+
+    pub fn metrics(&self) -> Option<&TenantMetricsResult> {
+        self.metrics_server().metrics.as_ref()
+    }
+
+    /// Tenant whose metrics are shown
+    pub fn metrics_tenant(&self) -> Option<&str> {
+        self.metrics_tenant.as_deref().or(self.tenant_id.as_deref())
+    }
+
+    /// Tenants seen in the instance and image lists since startup, and
+    /// those picked so far, sorted
+    pub fn known_tenants(&self) -> Vec<String> {
+        let picked = self
+            .tenant_id
+            .iter()
+            .chain(self.metrics_tenant.iter())
+            .chain(self.compare_tenants.iter());
+        let tenants: BTreeSet<&String> = self.seen_tenants.iter().chain(picked).collect();
+        tenants
+            .into_iter()
+            .filter(|tenant| !tenant.is_empty())
+            .cloned()
+            .collect()
+    }
+
+    /// Metrics buckets in display order
+    pub fn metrics_buckets(&self) -> Vec<&MetricsBucket> {
+        let mut rows: Vec<_> = self
+            .metrics()
+            .map_or(Vec::new(), |m| m.buckets.iter().collect());
+        self.metrics_sort
+            .apply(&mut rows, |column, a, b| match column {
+                MetricsColumn::Time => a.bucket_time.cmp(&b.bucket_time),
+                MetricsColumn::Invocations => a.invocation_count.cmp(&b.invocation_count),
+                MetricsColumn::Failed => a.failure_count.cmp(&b.failure_count),
+                MetricsColumn::SuccessRate => {
+                    sort::compare_f64(a.success_rate_percent, b.success_rate_percent)
+                }
+                MetricsColumn::Duration => {
+                    sort::compare_f64(a.avg_duration_seconds, b.avg_duration_seconds)
+                }
+                MetricsColumn::Memory => a.avg_memory_bytes.cmp(&b.avg_memory_bytes),
+            });
+        rows
+    }
+
+    /// Checkpoints on the current page in display order
+    pub fn visible_checkpoints(&self) -> Vec<&CheckpointSummary> {
+        let mut rows: Vec<_> = self.checkpoints.iter().collect();
+        self.checkpoint_sort
+            .apply(&mut rows, |column, a, b| match column {
+                CheckpointColumn::Time => a.created_at.cmp(&b.created_at),
+                CheckpointColumn::Size => a.data_size_bytes.cmp(&b.data_size_bytes),
+            });
+        rows
+    }
+
+    /// Sort the current table by its next column
+    pub fn cycle_sort(&mut self) {
+        self.change_sort(|app| match app.sort_target() {
+            Some(Tab::Instances) => app.instance_sort.cycle(),
+            Some(Tab::Images) => app.image_sort.cycle(),
+            Some(Tab::Metrics) => app.metrics_sort.cycle(),
+            Some(Tab::Health) => {}
+            None => app.checkpoint_sort.cycle(),
+        });
+    }
+
+    /// Flip the sort direction of the current table
+    pub fn reverse_sort(&mut self) {
+        self.change_sort(|app| match app.sort_target() {
+            Some(Tab::Instances) => app.instance_sort.reverse(),
+            Some(Tab::Images) => app.image_sort.reverse(),
+            Some(Tab::Metrics) => app.metrics_sort.reverse(),
+            Some(Tab::Health) => {}
+            None => app.checkpoint_sort.reverse(),
+        });
+    }
+
+    /// Tab whose table the sort keys apply to; `None` for the checkpoints
+    /// list
+    fn sort_target(&self) -> Option<Tab> {
+        match self.view_mode {
+            ViewMode::CheckpointsList => None,
+            _ => Some(self.tab),
+        }
+    }
+
+    /// Apply a sort change, keeping the same row selected
+    fn change_sort(&mut self, change: impl FnOnce(&mut Self)) {
+        let selected = self.sort_selection();
+        let key = self.sort_keys().get(selected).cloned();
+        let server_order = self.instance_sort.server_order();
+        change(self);
+
+        // A new server order lists other instances; start over from the top
+        if self.instance_sort.server_order() != server_order {
+            self.instances_offset = 0;
+            self.instances_selected = 0;
+            self.refresh();
+            return;
+        }
+        let Some(index) = key.and_then(|key| self.sort_keys().iter().position(|k| *k == key))
+        else {
+            return;
+        };
+        match self.sort_target() {
+            Some(Tab::Instances) => self.instances_selected = index,
+            Some(Tab::Images) => self.images_selected = index,
+            Some(Tab::Metrics) => self.metrics_selected = index,
+            Some(Tab::Health) => {}
+            None => self.checkpoints_selected = index,
+        }
+    }
+
+    /// Selected row of the sorted table
+    fn sort_selection(&self) -> usize {
+        match self.sort_target() {
+            Some(Tab::Instances) => self.instances_selected,
+            Some(Tab::Images) => self.images_selected,
+            Some(Tab::Metrics) => self.metrics_selected,
+            Some(Tab::Health) => 0,
+            None => self.checkpoints_selected,
+        }
+    }
+
+    /// Identity of each row of the sorted table, in display order
+    fn sort_keys(&self) -> Vec<String> {
+        match self.sort_target() {
+            Some(Tab::Instances) => self
+                .visible_instances()
+                .iter()
+                .map(|(server, inst)| format!("{}/{}", server, inst.instance_id))
+                .collect(),
+            Some(Tab::Images) => self
+                .visible_images()
+                .iter()
+                .map(|(server, img)| format!("{}/{}", server, img.image_id))
+                .collect(),
+            Some(Tab::Metrics) => self
+                .metrics_buckets()
+                .iter()
+                .map(|bucket| bucket.bucket_time.to_rfc3339())
+                .collect(),
+            Some(Tab::Health) => Vec::new(),
+            None => self
+                .visible_checkpoints()
+                .iter()
+                .map(|cp| cp.checkpoint_id.clone())
+                .collect(),
+        }
+    }
+
+    /// Keep the images selection within the visible rows
+    fn clamp_images_selection(&mut self) {
+        let len = self.visible_images().len();
+        if self.images_selected >= len && len > 0 {
+            self.images_selected = len - 1;
+        }
+    }
+
+    /// Keep the instances selection within the visible rows
+    fn clamp_instances_selection(&mut self) {
+        let len = self.visible_instances().len();
+        if self.instances_selected >= len && len > 0 {
+            self.instances_selected = len - 1;
+        }
+    }
+
+    /// Select next item in current list
+    pub fn next_item(&mut self) {
+        match self.tab {
+            Tab::Instances => {
+                let len = self.visible_instances().len();
+                if len > 0 {
+                    self.instances_selected = (self.instances_selected + 1) % len;
+                }
+            }
+            Tab::Images => {
+                let len = self.visible_images().len();
+                if len > 0 {
+                    self.images_selected = (self.images_selected + 1) % len;
+                }
+            }
+            Tab::Metrics => {
+                if let Some(metrics) = self.metrics() {
+                    if !metrics.buckets.is_empty() {
+                        self.metrics_selected = (self.metrics_selected + 1) % metrics.buckets.len();
+                    }
+                }
+            }
+            Tab::Health => {}
+        }
+    }
+
+    /// Select previous item in current list
+    pub fn previous_item(&mut self) {
+        match self.tab {
+            Tab::Instances => {
+                let len = self.visible_instances().len();
+                if len > 0 {
+                    self.instances_selected =
+                        self.instances_selected.checked_sub(1).unwrap_or(len - 1);
+                }
+            }
+            Tab::Images => {
+                let len = self.visible_images().len();
+                if len > 0 {
+                    self.images_selected = self.images_selected.checked_sub(1).unwrap_or(len - 1);
+                }
+            }
+            Tab::Metrics => {
+                if let Some(metrics) = self.metrics() {
+                    if !metrics.buckets.is_empty() {
+                        self.metrics_selected = self
+                            .metrics_selected
+                            .checked_sub(1)
+                            .unwrap_or(metrics.buckets.len() - 1);
+                    }
+                }
+            }
+            Tab::Health => {}
+        }
+    }
+
+    /// Move the cursor of the current list by a page or to one end
+    pub fn jump(&mut self, jump: Jump) {
+        let (len, page) = match (self.view_mode, self.tab) {
+            (ViewMode::CheckpointsList, _) => {
+                (self.checkpoints.len(), self.checkpoints_view.page())
+            }
+            (ViewMode::List, Tab::Instances) => {
+                (self.visible_instances().len(), self.instances_view.page())
             }
+            (ViewMode::List, Tab::Images) => (self.visible_images().len(), self.images_view.page()),
+            (ViewMode::List, Tab::Metrics) => {
+                (self.metrics_buckets().len(), self.metrics_view.page())
+            }
+            (ViewMode::ImageMetrics, _) => match &self.image_metrics {
+                Some(metrics) => (metrics.buckets.len(), self.image_metrics_view.page()),
+                None => return,
+            },
+            _ => return,
+        };
+        let selected = match (self.view_mode, self.tab) {
+            (ViewMode::CheckpointsList, _) => &mut self.checkpoints_selected,
+            (ViewMode::ImageMetrics, _) => &mut self.image_metrics_selected,
+            (_, Tab::Instances) => &mut self.instances_selected,
+            (_, Tab::Images) => &mut self.images_selected,
+            _ => &mut self.metrics_selected,
         };
+        let last = len.saturating_sub(1);
+        *selected = match jump {
+            Jump::PageDown => (*selected + page).min(last),
+            Jump::PageUp => selected.saturating_sub(page),
+            Jump::First => 0,
+            Jump::Last => last,
+        };
+    }
 
-        if let Err(e) = sdk.connect().await {
-            self.error = Some(format!("Connection failed: {}", e));
-            self.connected = false;
+    /// Cycle through status filters
+    pub fn cycle_status_filter(&mut self) {
+        self.status_filter = self.status_filter.next();
+        self.instances_offset = 0;
+        self.instances_selected = 0;
+    }
+
+    /// Open the profile switcher on the active profile
+    pub fn open_profile_picker(&mut self) {
+        if self.profiles.is_empty() {
+            self.show_flash("No profiles configured".to_string(), true);
             return;
         }
+        let current = self
+            .profile
+            .as_ref()
+            .and_then(|name| self.profiles.iter().position(|p| p == name))
+            .unwrap_or(0);
+        self.profile_picker = Some(current);
+    }
+
+    pub fn close_profile_picker(&mut self) {
+        self.profile_picker = None;
+    }
+
+    pub fn next_profile(&mut self) {
+        if let Some(ref mut selected) = self.profile_picker {
+            *selected = (*selected + 1) % self.profiles.len();
+        }
+    }
 
-        self.connected = true;
+    pub fn previous_profile(&mut self) {
+        if let Some(ref mut selected) = self.profile_picker {
+            *selected = selected.checked_sub(1).unwrap_or(self.profiles.len() - 1);
+        }
+    }
 
-        // Fetch health
-        match sdk.health_check().await {
-            Ok(health) => self.health = Some(health),
-            Err(e) => {
-                self.error = Some(format!("Health check failed: {}", e));
+    /// Request a switch to the selected profile
+    pub fn select_profile(&mut self) {
+        if let Some(selected) = self.profile_picker.take() {
+            let name = self.profiles[selected].clone();
+            if self.profile.as_ref() != Some(&name) {
+                self.requested_profile = Some(name);
             }
         }
+    }
 
-        // Fetch instances
-        let options = ListInstancesOptions {
-            tenant_id: self.tenant_id.clone(),
-            status: self.status_filter.to_instance_status(),
-            limit: 100,
-            ..Default::default()
+    /// Carry view state over from the app this one replaces
+    pub fn inherit_view(&mut self, previous: &App) {
+        self.tab = previous.tab;
+        self.status_filter = previous.status_filter;
+        self.instance_filter = previous.instance_filter.clone();
+        self.metrics_granularity = previous.metrics_granularity;
+        self.metrics_range = previous.metrics_range;
+        self.instance_sort = previous.instance_sort;
+        self.image_sort = previous.image_sort;
+        self.checkpoint_sort = previous.checkpoint_sort;
+        self.metrics_sort = previous.metrics_sort;
+        self.query_history = previous.query_history.clone();
+    }
+
+    /// Open a text prompt
+    pub fn open_prompt(&mut self, kind: PromptKind) {
+        let initial = match kind {
+            PromptKind::Search => self.instance_filter.search.clone(),
+            PromptKind::Filter => self.instance_filter.bar_text(),
+            PromptKind::Query => match self.detail_tree_mut() {
+                Some(tree) => tree
+                    .query()
+                    .map(|query| query.text.clone())
+                    .unwrap_or_default(),
+                None => return,
+            },
+            PromptKind::Export => {
+                self.export_part = ExportPart::All;
+                match self.export_path(ExportPart::All) {
+                    Some(path) => path,
+                    None => return,
+                }
+            }
+            PromptKind::ExportBundle => match (&self.view_mode, &self.instance_detail) {
+                (ViewMode::InstanceDetail, Some(info)) => info.instance_id.clone(),
+                _ => return,
+            },
+            PromptKind::MetricsRange => self.metrics_range.text(),
+            PromptKind::Tenant => String::new(),
         };
+        self.prompt = Some(Prompt {
+            kind,
+            input: TextInput::new(initial.clone()),
+            error: None,
+            initial,
+            history: None,
+        });
+
+        if kind == PromptKind::Tenant {
+            self.prompt_changed();
+            // Start on the current scope
+            let current = match &self.tenant_id {
+                Some(tenant) => TenantChoice::Known(tenant.clone()),
+                None => TenantChoice::All,
+            };
+            let choices = &mut self.tenant_choices;
+            choices.selected = choices
+                .rows
+                .iter()
+                .position(|row| *row == current)
+                .unwrap_or(0);
+        }
+    }
 
-        match sdk.list_instances(options).await {
-            Ok(result) => {
-                self.instances = result.instances;
-                self.instances_total = result.total_count;
-                if self.instances_selected >= self.instances.len() && !self.instances.is_empty() {
-                    self.instances_selected = self.instances.len() - 1;
+    /// Apply the text of the open prompt as the user types
+    pub fn prompt_changed(&mut self) {
+        let Some(prompt) = self.prompt.as_mut() else {
+            return;
+        };
+        match prompt.kind {
+            PromptKind::Search => {
+                self.instance_filter.search = prompt.input.value().to_string();
+                self.clamp_instances_selection();
+            }
+            PromptKind::Query => {
+                let text = prompt.input.value().trim().to_string();
+                let query = if text.is_empty() {
+                    Ok(None)
+                } else {
+                    Query::parse(&text).map(Some)
+                };
+                match query {
+                    Ok(query) => {
+                        prompt.error = None;
+                        if let Some(tree) = self.detail_tree_mut() {
+                            tree.set_query(query);
+                        }
+                    }
+                    // The tree keeps the last valid query
+                    Err(e) => prompt.error = Some(e),
                 }
             }
-            Err(e) => {
-                self.error = Some(format!("Failed to list instances: {}", e));
+            PromptKind::Tenant => {
+                let text = prompt.input.value().trim().to_string();
+                let known = self.known_tenants();
+                let rows = if text.is_empty() {
+                    std::iter::once(TenantChoice::All)
+                        .chain(known.into_iter().map(TenantChoice::Known))
+                        .collect()
+                } else {
+                    let mut rows: Vec<_> = fuzzy::rank(&text, known.iter().map(String::as_str))
+                        .into_iter()
+                        .map(|tenant| TenantChoice::Known(tenant.to_string()))
+                        .collect();
+                    if !known.contains(&text) {
+                        rows.push(TenantChoice::Entered(text));
+                    }
+                    rows
+                };
+                self.tenant_choices = TenantChoices { rows, selected: 0 };
             }
+            PromptKind::Filter
+            | PromptKind::Export
+            | PromptKind::ExportBundle
+            | PromptKind::MetricsRange => {}
         }
+    }
 
-        // Fetch images
-        let options = ListImagesOptions {
-            tenant_id: self.tenant_id.clone(),
-            limit: 100,
-            ..Default::default()
+    /// Select the next row of the tenant selector
+    pub fn next_tenant_choice(&mut self) {
+        let choices = &mut self.tenant_choices;
+        if !choices.rows.is_empty() {
+            choices.selected = (choices.selected + 1) % choices.rows.len();
+        }
+    }
+
+    /// Select the previous row of the tenant selector
+    pub fn previous_tenant_choice(&mut self) {
+        let choices = &mut self.tenant_choices;
+        if !choices.rows.is_empty() {
+            choices.selected = choices
+                .selected
+                .checked_sub(1)
+                .unwrap_or(choices.rows.len() - 1);
+        }
+    }
+
+    /// Show the previous query from the history in the open query prompt
+    pub fn history_previous(&mut self) {
+        let Some(prompt) = self.prompt.as_mut() else {
+            return;
+        };
+        if prompt.kind != PromptKind::Query || self.query_history.is_empty() {
+            return;
+        }
+        let (index, draft) = match prompt.history.take() {
+            Some((index, draft)) => (index.saturating_sub(1), draft),
+            None => (
+                self.query_history.len() - 1,
+                prompt.input.value().to_string(),
+            ),
+        };
+        prompt.input.set(self.query_history[index].clone());
+        prompt.history = Some((index, draft));
+        self.prompt_changed();
+    }
+
+    /// Show the next query from the history, or the text typed before
+    /// browsing it
+    pub fn history_next(&mut self) {
+        let Some(prompt) = self.prompt.as_mut() else {
+            return;
+        };
+        let Some((index, draft)) = prompt.history.take() else {
+            return;
         };
+        if index + 1 < self.query_history.len() {
+            prompt.input.set(self.query_history[index + 1].clone());
+            prompt.history = Some((index + 1, draft));
+        } else {
+            prompt.input.set(draft);
+        }
+        self.prompt_changed();
+    }
 
-        match sdk.list_images(options).await {
-            Ok(result) => {
-                self.images = result.images;
-                self.images_total = result.total_count;
-                if self.images_selected >= self.images.len() && !self.images.is_empty() {
-                    self.images_selected = self.images.len() - 1;
+    /// Accept the open prompt
+    pub fn submit_prompt(&mut self) {
+        let Some(prompt) = self.prompt.take() else {
+            return;
+        };
+
+        match prompt.kind {
+            PromptKind::Search => {
+                self.instance_filter.search = prompt.input.value().trim().to_string();
+                self.instances_selected = 0;
+            }
+            PromptKind::Tenant => {
+                match self.tenant_choices.rows.get(self.tenant_choices.selected) {
+                    Some(TenantChoice::All) => self.set_tenant(None),
+                    Some(TenantChoice::Known(tenant) | TenantChoice::Entered(tenant)) => {
+                        self.set_tenant(Some(tenant.clone()))
+                    }
+                    None => {}
+                }
+            }
+            PromptKind::MetricsRange => match MetricsRange::parse(prompt.input.value()) {
+                Ok(range) => self.set_metrics_range(range),
+                Err(e) => {
+                    self.show_flash(e, true);
+                    self.prompt = Some(prompt);
+                }
+            },
+            PromptKind::Filter => match self.instance_filter.parse_bar(prompt.input.value()) {
+                Ok(filter) => {
+                    self.instance_filter = filter;
+                    self.instances_offset = 0;
+                    self.instances_selected = 0;
+                    self.refresh();
+                }
+                Err(e) => {
+                    // Keep the prompt open so the input can be fixed
+                    self.show_flash(e, true);
+                    self.prompt = Some(prompt);
+                }
+            },
+            PromptKind::Query => {
+                if let Some(e) = prompt.error.clone() {
+                    self.show_flash(e, true);
+                    self.prompt = Some(prompt);
+                    return;
+                }
+                let text = prompt.input.value().trim();
+                if !text.is_empty() && self.query_history.last().map(String::as_str) != Some(text) {
+                    self.query_history.push(text.to_string());
+                }
+            }
+            PromptKind::Export => {
+                let text = prompt.input.value().trim();
+                if text.is_empty() {
+                    self.show_flash("Enter a file to export to".to_string(), true);
+                    self.prompt = Some(prompt);
+                    return;
                 }
+                let Some(value) = self.export_value(self.export_part) else {
+                    self.show_flash(
+                        format!("Nothing to export: no {}", self.export_part.as_str()),
+                        true,
+                    );
+                    return;
+                };
+                let path = export::expand_path(text);
+                self.show_flash(format!("Exporting to {}…", path.display()), false);
+                self.servers[self.detail_server].send(Request::ExportPayload { path, value });
             }
-            Err(e) => {
-                self.error = Some(format!("Failed to list images: {}", e));
+            PromptKind::ExportBundle => {
+                let text = prompt.input.value().trim();
+                let Some(instance_id) = self
+                    .instance_detail
+                    .as_ref()
+                    .filter(|_| !text.is_empty())
+                    .map(|info| info.instance_id.clone())
+                else {
+                    return;
+                };
+                self.show_flash(format!("Exporting {}…", instance_id), false);
+                self.servers[self.detail_server].send(Request::ExportBundle {
+                    instance_id,
+                    path: export::expand_path(text),
+                });
             }
         }
+    }
 
-        // Fetch metrics (requires tenant_id)
-        if let Some(ref tenant_id) = self.tenant_id {
-            let options =
-                GetTenantMetricsOptions::new(tenant_id).with_granularity(self.metrics_granularity);
-
-            match sdk.get_tenant_metrics(options).await {
-                Ok(result) => {
-                    let bucket_count = result.buckets.len();
-                    self.metrics = Some(result);
-                    if self.metrics_selected >= bucket_count && bucket_count > 0 {
-                        self.metrics_selected = bucket_count - 1;
-                    }
-                }
-                Err(e) => {
-                    self.error = Some(format!("Failed to get metrics: {}", e));
+    /// Copy the full ID of the selected row or open detail to the clipboard
+    pub fn yank_id(&mut self) {
+        let target = match self.view_mode {
+            ViewMode::List => match self.tab {
+                Tab::Instances => self
+                    .selected_instance()
+                    .map(|(_, inst)| ("instance ID", inst.instance_id.clone())),
+                Tab::Images => self
+                    .visible_images()
+                    .get(self.images_selected)
+                    .map(|(_, img)| ("image ID", img.image_id.clone())),
+                Tab::Metrics | Tab::Health => None,
+            },
+            ViewMode::InstanceDetail => self
+                .instance_detail
+                .as_ref()
+                .map(|info| ("instance ID", info.instance_id.clone())),
+            ViewMode::CheckpointsList => self
+                .visible_checkpoints()
+                .get(self.checkpoints_selected)
+                .map(|cp| ("checkpoint ID", cp.checkpoint_id.clone())),
+            ViewMode::CheckpointDetail => self
+                .checkpoint_detail
+                .as_ref()
+                .map(|cp| ("checkpoint ID", cp.checkpoint_id.clone())),
+            ViewMode::CheckpointDiff => self
+                .checkpoint_diff
+                .as_ref()
+                .and_then(|diff| diff.changes.get(diff.selected()))
+                .map(|change| ("path", change.path.clone())),
+            ViewMode::Timeline => self.timeline.as_ref().and_then(|timeline| {
+                match &timeline.events.get(timeline.selected())?.kind {
+                    EventKind::Checkpoint { id, .. } => Some(("checkpoint ID", id.clone())),
+                    _ => Some(("instance ID", timeline.instance_id.clone())),
                 }
-            }
+            }),
+            ViewMode::ImageMetrics => self
+                .metrics_image
+                .as_ref()
+                .map(|img| ("image ID", img.image_id.clone())),
+        };
+        if let Some((what, text)) = target {
+            let message = format!("Copied {} {}", what, text);
+            self.copy_to_clipboard(&text, message);
         }
+    }
 
-        self.last_refresh = Some(Instant::now());
+    /// Copy the payload of the open detail view to the clipboard as JSON
+    pub fn yank_payload(&mut self) {
+        let Some(value) = self.export_value(ExportPart::All) else {
+            return;
+        };
+        let text = serde_json::to_string_pretty(&value).unwrap_or_default();
+        let message = format!("Copied {} of JSON", format_bytes(text.len() as u64));
+        self.copy_to_clipboard(&text, message);
     }
 
-    /// Check if we should auto-refresh
-    pub fn should_refresh(&self) -> bool {
-        match self.last_refresh {
-            Some(last) => last.elapsed() >= self.refresh_interval,
-            None => true,
+    fn copy_to_clipboard(&mut self, text: &str, message: String) {
+        match clipboard::copy(text) {
+            Ok(()) => self.show_flash(message, false),
+            Err(e) => self.show_flash(format!("Copy failed: {}", e), true),
         }
     }
 
-    /// Switch to next tab
-    pub fn next_tab(&mut self) {
-        self.tab = match self.tab {
-            Tab::Instances => Tab::Images,
-            Tab::Images => Tab::Metrics,
-            Tab::Metrics => Tab::Health,
-            Tab::Health => Tab::Instances,
-        };
+    /// Default export path of `part` of the open detail view, or `None`
+    /// when the view has no such part
+    fn export_path(&self, part: ExportPart) -> Option<String> {
+        match self.view_mode {
+            ViewMode::InstanceDetail => {
+                let info = self.instance_detail.as_ref()?;
+                let name = match part {
+                    ExportPart::All => "instance",
+                    ExportPart::Input => info.input.as_ref().map(|_| "input")?,
+                    ExportPart::Output => info.output.as_ref().map(|_| "output")?,
+                    ExportPart::Error => info.error.as_ref().map(|_| "error")?,
+                    ExportPart::Selected => {
+                        self.instance_tree.as_ref()?.selected_pointer()?;
+                        "selection"
+                    }
+                };
+                Some(format!("{}/{}.json", info.instance_id, name))
+            }
+            ViewMode::CheckpointDetail => {
+                let checkpoint = self.checkpoint_detail.as_ref()?;
+                let suffix = match part {
+                    ExportPart::All => "",
+                    ExportPart::Selected => {
+                        self.checkpoint_tree.as_ref()?.selected_pointer()?;
+                        "-selection"
+                    }
+                    _ => return None,
+                };
+                Some(format!(
+                    "{}/{}{}.json",
+                    checkpoint.instance_id, checkpoint.checkpoint_id, suffix
+                ))
+            }
+            _ => None,
+        }
     }
 
-    /// Switch to previous tab
-    pub fn previous_tab(&mut self) {
-        self.tab = match self.tab {
-            Tab::Instances => Tab::Health,
-            Tab::Images => Tab::Instances,
-            Tab::Metrics => Tab::Images,
-            Tab::Health => Tab::Metrics,
+    /// Payload of `part` of the open detail view
+    fn export_value(&self, part: ExportPart) -> Option<serde_json::Value> {
+        let tree = match self.view_mode {
+            ViewMode::InstanceDetail => {
+                let info = self.instance_detail.as_ref()?;
+                match part {
+                    ExportPart::All => return serde_json::to_value(info).ok(),
+                    ExportPart::Input => return info.input.clone(),
+                    ExportPart::Output => return info.output.clone(),
+                    ExportPart::Error => return info.error.clone().map(serde_json::Value::String),
+                    ExportPart::Selected => self.instance_tree.as_ref()?,
+                }
+            }
+            ViewMode::CheckpointDetail => {
+                let checkpoint = self.checkpoint_detail.as_ref()?;
+                match part {
+                    ExportPart::All => return Some(checkpoint.data.clone()),
+                    ExportPart::Selected => self.checkpoint_tree.as_ref()?,
+                    _ => return None,
+                }
+            }
+            _ => return None,
         };
+        tree.selected_value().cloned()
     }
 
-    /// Set tab by index
-    pub fn set_tab(&mut self, index: usize) {
-        self.tab = match index {
-            0 => Tab::Instances,
-            1 => Tab::Images,
-            2 => Tab::Metrics,
-            3 => Tab::Health,
-            _ => Tab::Instances,
+    /// Export the next part the open detail view has; a default path
+    /// follows the part
+    pub fn next_export_part(&mut self) {
+        let current = self.export_part;
+        let start = ExportPart::ALL
+            .iter()
+            .position(|part| *part == current)
+            .unwrap_or(0);
+        let Some((next, path)) = (1..=ExportPart::ALL.len())
+            .map(|i| ExportPart::ALL[(start + i) % ExportPart::ALL.len()])
+            .find_map(|part| self.export_path(part).map(|path| (part, path)))
+        else {
+            return;
         };
+        let previous = self.export_path(current);
+        self.export_part = next;
+        if let Some(prompt) = self.prompt.as_mut() {
+            if previous.as_deref() == Some(prompt.input.value()) {
+                prompt.input = TextInput::new(path);
+            }
+        }
     }
 
-    /// Select next item in current list
-    pub fn next_item(&mut self) {
-        match self.tab {
-            Tab::Instances => {
-                if !self.instances.is_empty() {
-                    self.instances_selected = (self.instances_selected + 1) % self.instances.len();
+    /// Close the open prompt, discarding live changes
+    pub fn cancel_prompt(&mut self) {
+        if let Some(prompt) = self.prompt.take() {
+            match prompt.kind {
+                // Search and queries apply live; restore the previous text
+                PromptKind::Search => {
+                    self.instance_filter.search = prompt.initial;
+                    self.clamp_instances_selection();
                 }
-            }
-            Tab::Images => {
-                if !self.images.is_empty() {
-                    self.images_selected = (self.images_selected + 1) % self.images.len();
-                }
-            }
-            Tab::Metrics => {
-                if let Some(ref metrics) = self.metrics {
-                    if !metrics.buckets.is_empty() {
-                        self.metrics_selected = (self.metrics_selected + 1) % metrics.buckets.len();
+                PromptKind::Query => {
+                    let query = Query::parse(&prompt.initial).ok();
+                    if let Some(tree) = self.detail_tree_mut() {
+                        tree.set_query(query);
                     }
                 }
+                PromptKind::Filter
+                | PromptKind::Export
+                | PromptKind::ExportBundle
+                | PromptKind::MetricsRange
+                | PromptKind::Tenant => {}
             }
-            Tab::Health => {}
         }
     }
 
-    /// Select previous item in current list
-    pub fn previous_item(&mut self) {
-        match self.tab {
-            Tab::Instances => {
-                if !self.instances.is_empty() {
-                    self.instances_selected = self
-                        .instances_selected
-                        .checked_sub(1)
-                        .unwrap_or(self.instances.len() - 1);
+    /// Scope the lists and metrics to `tenant`, or show all tenants
+    fn set_tenant(&mut self, tenant: Option<String>) {
+        if tenant == self.tenant_id {
+            return;
+        }
+        self.show_flash(
+            match &tenant {
+                Some(tenant) => format!("Showing tenant {}", tenant),
+                None => "Showing all tenants".to_string(),
+            },
+            false,
+        );
+        self.tenant_id = tenant;
+        self.running_scanned = None;
+        self.metrics_tenant = None;
+        self.compare_tenants.clear();
+        self.instances_offset = 0;
+        self.instances_selected = 0;
+        self.images_offset = 0;
+        self.images_selected = 0;
+        self.reload_metrics();
+    }
+
+    /// Move to the next page of the current list
+    pub fn next_page(&mut self) {
+        match self.view_mode {
+            ViewMode::List => match self.tab {
+                // Stuck instances come from one scan and are not paged
+                Tab::Instances if self.status_filter == StatusFilter::Stuck => {}
+                Tab::Instances => {
+                    if self.instances_offset + PAGE_SIZE < self.max_total(|s| s.instances_total) {
+                        self.instances_offset += PAGE_SIZE;
+                        self.instances_selected = 0;
+                        self.refresh();
+                    }
                 }
-            }
-            Tab::Images => {
-                if !self.images.is_empty() {
-                    self.images_selected = self
-                        .images_selected
-                        .checked_sub(1)
-                        .unwrap_or(self.images.len() - 1);
+                Tab::Images => {
+                    if self.images_offset + PAGE_SIZE < self.max_total(|s| s.images_total) {
+                        self.images_offset += PAGE_SIZE;
+                        self.images_selected = 0;
+                        self.refresh();
+                    }
                 }
+                Tab::Metrics | Tab::Health => {}
+            },
+            ViewMode::CheckpointsList
+                if self.checkpoints_offset + PAGE_SIZE < self.checkpoints_total =>
+            {
+                self.checkpoints_offset += PAGE_SIZE;
+                self.fetch_checkpoints();
             }
-            Tab::Metrics => {
-                if let Some(ref metrics) = self.metrics {
-                    if !metrics.buckets.is_empty() {
-                        self.metrics_selected = self
-                            .metrics_selected
-                            .checked_sub(1)
-                            .unwrap_or(metrics.buckets.len() - 1);
+            _ => {}
+        }
+    }
+
+    /// Move to the previous page of the current list
+    pub fn previous_page(&mut self) {
+        match self.view_mode {
+            ViewMode::List => match self.tab {
+                Tab::Instances => {
+                    if self.instances_offset > 0 {
+                        self.instances_offset = self.instances_offset.saturating_sub(PAGE_SIZE);
+                        self.instances_selected = 0;
+                        self.refresh();
+                    }
+                }
+                Tab::Images => {
+                    if self.images_offset > 0 {
+                        self.images_offset = self.images_offset.saturating_sub(PAGE_SIZE);
+                        self.images_selected = 0;
+                        self.refresh();
                     }
                 }
+                Tab::Metrics | Tab::Health => {}
+            },
+            ViewMode::CheckpointsList if self.checkpoints_offset > 0 => {
+                self.checkpoints_offset = self.checkpoints_offset.saturating_sub(PAGE_SIZE);
+                self.fetch_checkpoints();
             }
-            Tab::Health => {}
+            _ => {}
         }
     }
 
-    /// Cycle through status filters
-    pub fn cycle_status_filter(&mut self) {
-        self.status_filter = self.status_filter.next();
+    /// Largest list total on the shown servers; pages are shared, so this
+    /// bounds paging
+    fn max_total(&self, total: impl Fn(&Server) -> u32) -> u32 {
+        self.shown_servers()
+            .map(|(_, s)| total(s))
+            .max()
+            .unwrap_or(0)
     }
 
     /// Toggle metrics granularity between hourly and daily
@@ -413,114 +2174,444 @@ impl App {
         self.metrics_selected = 0;
     }
 
-    /// Open instance detail view for the selected instance
-    pub async fn open_instance_detail(&mut self) {
-        if self.instances.is_empty() {
+    /// Regroup the open image metrics hourly or daily, leaving the Metrics
+    /// tab as it is
+    pub fn toggle_image_metrics_granularity(&mut self) {
+        self.image_metrics_granularity = match self.image_metrics_granularity {
+            MetricsGranularity::Hourly => MetricsGranularity::Daily,
+            MetricsGranularity::Daily => MetricsGranularity::Hourly,
+        };
+        if let Some(metrics) = self.image_metrics.as_mut() {
+            metrics.group(self.image_metrics_granularity);
+            self.image_metrics_selected = 0;
+        }
+    }
+
+    /// Open the tenant picker on the tenant shown, with the compared
+    /// tenants marked
+    pub fn open_tenant_picker(&mut self) {
+        let tenants = self.known_tenants();
+        if tenants.is_empty() {
+            self.show_flash("No tenants seen yet".to_string(), true);
             return;
         }
+        let selected = self
+            .metrics_tenant()
+            .and_then(|current| tenants.iter().position(|t| t == current))
+            .unwrap_or(0);
+        self.tenant_picker = Some(TenantPicker {
+            tenants,
+            selected,
+            marked: self.compare_tenants.clone(),
+        });
+    }
 
-        let instance_id = &self.instances[self.instances_selected].instance_id;
+    pub fn close_tenant_picker(&mut self) {
+        self.tenant_picker = None;
+    }
 
-        let sdk = match self.create_sdk() {
-            Ok(sdk) => sdk,
-            Err(e) => {
-                self.error = Some(format!("Failed to create SDK: {}", e));
-                return;
+    pub fn next_tenant(&mut self) {
+        if let Some(ref mut picker) = self.tenant_picker {
+            picker.selected = (picker.selected + 1) % picker.tenants.len();
+        }
+    }
+
+    pub fn previous_tenant(&mut self) {
+        if let Some(ref mut picker) = self.tenant_picker {
+            picker.selected = picker
+                .selected
+                .checked_sub(1)
+                .unwrap_or(picker.tenants.len() - 1);
+        }
+    }
+
+    /// Mark or unmark the selected tenant for comparison
+    pub fn toggle_tenant_mark(&mut self) {
+        if let Some(ref mut picker) = self.tenant_picker {
+            let tenant = &picker.tenants[picker.selected];
+            match picker.marked.iter().position(|t| t == tenant) {
+                Some(index) => {
+                    picker.marked.remove(index);
+                }
+                None => picker.marked.push(tenant.clone()),
             }
-        };
+        }
+    }
+
+    /// Show the metrics of the selected tenant
+    pub fn select_tenant(&mut self) {
+        if let Some(picker) = self.tenant_picker.take() {
+            self.metrics_tenant = Some(picker.tenants[picker.selected].clone());
+            self.compare_tenants.clear();
+            self.reload_metrics();
+        }
+    }
 
-        if let Err(e) = sdk.connect().await {
-            self.error = Some(format!("Connection failed: {}", e));
+    /// Compare the metrics of the marked tenants
+    pub fn compare_marked_tenants(&mut self) {
+        let Some(picker) = self.tenant_picker.as_ref() else {
+            return;
+        };
+        if picker.marked.len() < 2 {
+            self.show_flash("Mark two or more tenants with Space to compare them", true);
             return;
         }
+        let mut marked = picker.marked.clone();
+        marked.sort();
+        self.tenant_picker = None;
+        self.compare_tenants = marked;
+        self.reload_metrics();
+    }
+
+    /// Drop the metrics shown and fetch them again for the current tenants
+    fn reload_metrics(&mut self) {
+        for server in &mut self.servers {
+            server.metrics = None;
+            server.comparison.clear();
+        }
+        self.metrics_selected = 0;
+        self.refresh();
+    }
 
-        match sdk.get_instance_status(instance_id).await {
-            Ok(info) => {
-                self.instance_detail = Some(info);
-                self.view_mode = ViewMode::InstanceDetail;
-                self.detail_scroll = 0;
-            }
-            Err(e) => {
-                self.error = Some(format!("Failed to get instance details: {}", e));
+    /// Open the metrics range picker on the current range
+    pub fn open_range_picker(&mut self) {
+        let presets = MetricsRange::presets();
+        let current = presets
+            .iter()
+            .position(|range| *range == self.metrics_range)
+            .unwrap_or(presets.len());
+        self.range_picker = Some(current);
+    }
+
+    pub fn close_range_picker(&mut self) {
+        self.range_picker = None;
+    }
+
+    pub fn next_range(&mut self) {
+        if let Some(ref mut selected) = self.range_picker {
+            *selected = (*selected + 1) % (MetricsRange::presets().len() + 1);
+        }
+    }
+
+    pub fn previous_range(&mut self) {
+        if let Some(ref mut selected) = self.range_picker {
+            *selected = selected
+                .checked_sub(1)
+                .unwrap_or(MetricsRange::presets().len());
+        }
+    }
+
+    /// Apply the selected preset, or prompt for a custom range
+    pub fn select_range(&mut self) {
+        if let Some(selected) = self.range_picker.take() {
+            match MetricsRange::presets().get(selected) {
+                Some(range) => self.set_metrics_range(*range),
+                None => self.open_prompt(PromptKind::MetricsRange),
             }
         }
     }
 
-    /// Open checkpoints list for the current instance detail
-    pub async fn open_checkpoints_list(&mut self) {
-        let instance_id = match &self.instance_detail {
-            Some(info) => info.instance_id.clone(),
+    /// Fetch metrics over `range`
+    fn set_metrics_range(&mut self, range: MetricsRange) {
+        self.metrics_range = range;
+        self.metrics_selected = 0;
+        self.refresh();
+        if self.view_mode == ViewMode::ImageMetrics {
+            self.fetch_image_metrics();
+        }
+    }
+
+    /// Open instance detail view for the selected instance
+    pub fn open_instance_detail(&mut self) {
+        let (server, instance_id) = match self.selected_instance() {
+            Some((server, inst)) => (server, inst.instance_id.clone()),
             None => return,
         };
 
-        let sdk = match self.create_sdk() {
-            Ok(sdk) => sdk,
-            Err(e) => {
-                self.error = Some(format!("Failed to create SDK: {}", e));
-                return;
-            }
+        self.detail_server = server;
+        self.instance_detail = None;
+        self.instance_tree = None;
+        self.follow = None;
+        self.view_mode = ViewMode::InstanceDetail;
+        self.view_seq += 1;
+        self.servers[server].send(Request::InstanceDetail {
+            seq: self.view_seq,
+            instance_id,
+        });
+    }
+
+    /// Open checkpoints list for the current instance detail
+    pub fn open_checkpoints_list(&mut self) {
+        if self.instance_detail.is_none() {
+            return;
+        }
+
+        self.checkpoints.clear();
+        self.checkpoints_total = 0;
+        self.checkpoints_offset = 0;
+        self.checkpoints_selected = 0;
+        self.checkpoints_marked.clear();
+        self.view_mode = ViewMode::CheckpointsList;
+        self.view_seq += 1;
+        self.fetch_checkpoints();
+    }
+
+    /// Start or stop following the open instance
+    pub fn toggle_follow(&mut self) {
+        let Some(info) = &self.instance_detail else {
+            return;
         };
+        if let Some(follow) = self.follow.take() {
+            if !follow.finished {
+                self.show_flash(format!("Stopped following {}", info.instance_id), false);
+            }
+        } else if info.status.is_terminal() {
+            let (status_text, _) = status_style(info.status);
+            self.show_flash(
+                format!(
+                    "{} is already {}",
+                    info.instance_id,
+                    status_text.to_lowercase()
+                ),
+                true,
+            );
+        } else {
+            let message = format!("Following {}", info.instance_id);
+            self.follow = Some(Follow::default());
+            self.show_flash(message, false);
+            self.poll_follow();
+        }
+    }
 
-        if let Err(e) = sdk.connect().await {
-            self.error = Some(format!("Connection failed: {}", e));
+    /// Poll the followed instance when the interval has passed
+    pub fn poll_follow(&mut self) {
+        if self.view_mode != ViewMode::InstanceDetail {
+            return;
+        }
+        let (Some(follow), Some(info)) = (self.follow.as_mut(), &self.instance_detail) else {
+            return;
+        };
+        if follow.finished
+            || follow.polling
+            || follow
+                .polled_at
+                .is_some_and(|at| at.elapsed() < FOLLOW_INTERVAL)
+        {
             return;
         }
 
-        let options = ListCheckpointsOptions::new().with_limit(100);
+        follow.polling = true;
+        follow.polled_at = Some(Instant::now());
+        self.servers[self.detail_server].send(Request::Follow {
+            seq: self.view_seq,
+            instance_id: info.instance_id.clone(),
+            since: follow.newest,
+        });
+    }
 
-        match sdk.list_checkpoints(&instance_id, options).await {
-            Ok(result) => {
-                self.checkpoints = result.checkpoints;
-                self.checkpoints_total = result.total_count;
-                self.checkpoints_selected = 0;
-                self.view_mode = ViewMode::CheckpointsList;
+    /// Apply a poll of the followed instance
+    fn follow_update(&mut self, info: InstanceInfo, checkpoints: Vec<CheckpointSummary>) {
+        let Some(follow) = self.follow.as_mut() else {
+            return;
+        };
+
+        // Checkpoints arrive newest first
+        for checkpoint in checkpoints.into_iter().rev() {
+            if !follow.seen.insert(checkpoint.checkpoint_id.clone()) {
+                continue;
+            }
+            if !matches!(follow.newest, Some(newest) if newest >= checkpoint.created_at) {
+                follow.newest = Some(checkpoint.created_at);
             }
-            Err(e) => {
-                self.error = Some(format!("Failed to list checkpoints: {}", e));
+            if follow.primed {
+                follow.log.push((
+                    checkpoint.created_at,
+                    FollowEvent::Checkpoint {
+                        id: checkpoint.checkpoint_id,
+                        size: checkpoint.data_size_bytes,
+                    },
+                ));
+            }
+        }
+        follow.primed = true;
+
+        if let Some(previous) = &self.instance_detail {
+            if previous.status != info.status {
+                follow.log.push((
+                    Utc::now(),
+                    FollowEvent::Status {
+                        from: previous.status,
+                        to: info.status,
+                    },
+                ));
             }
         }
+
+        if info.status.is_terminal() {
+            follow.finished = true;
+            let (status_text, _) = status_style(info.status);
+            self.show_flash(
+                format!("{} {}", info.instance_id, status_text.to_lowercase()),
+                info.status == InstanceStatus::Failed,
+            );
+            let _ = notify::bell();
+        }
+        show_tree(&mut self.instance_tree, instance_data(&info));
+        self.instance_detail = Some(info);
     }
 
-    /// Open checkpoint detail view for the selected checkpoint
-    pub async fn open_checkpoint_detail(&mut self) {
-        if self.checkpoints.is_empty() {
+    /// Open the metrics of the image selected in the Images tab
+    pub fn open_image_metrics(&mut self) {
+        let Some((server, image)) = self
+            .visible_images()
+            .get(self.images_selected)
+            .map(|(server, img)| (*server, (*img).clone()))
+        else {
             return;
-        }
+        };
+
+        self.detail_server = server;
+        self.metrics_image = Some(image);
+        self.image_metrics_granularity = self.metrics_granularity;
+        self.view_mode = ViewMode::ImageMetrics;
+        self.fetch_image_metrics();
+    }
+
+    /// List the instances of the open image over the metrics range
+    pub fn fetch_image_metrics(&mut self) {
+        let Some(image_id) = self.metrics_image.as_ref().map(|img| img.image_id.clone()) else {
+            return;
+        };
+        let (start, end) = self.metrics_range.bounds(Utc::now());
+
+        self.image_metrics = None;
+        self.image_metrics_selected = 0;
+        self.view_seq += 1;
+        self.servers[self.detail_server].send(Request::ImageInstances {
+            seq: self.view_seq,
+            tenant_id: self.tenant_id.clone(),
+            image_id,
+            start,
+            end,
+        });
+    }
+
+    /// Open the timeline of the open instance
+    pub fn open_timeline(&mut self) {
+        let instance_id = match &self.instance_detail {
+            Some(info) => info.instance_id.clone(),
+            None => return,
+        };
+
+        self.timeline = None;
+        self.view_mode = ViewMode::Timeline;
+        self.view_seq += 1;
+        self.servers[self.detail_server].send(Request::Timeline {
+            seq: self.view_seq,
+            instance_id,
+        });
+    }
 
-        let checkpoint = &self.checkpoints[self.checkpoints_selected];
+    /// Fetch the current page of checkpoints for the open instance
+    fn fetch_checkpoints(&mut self) {
+        let instance_id = match &self.instance_detail {
+            Some(info) => info.instance_id.clone(),
+            None => return,
+        };
+
+        self.checkpoints_state = LoadState::Loading;
+        self.servers[self.detail_server].send(Request::Checkpoints {
+            seq: self.view_seq,
+            instance_id,
+            offset: self.checkpoints_offset,
+        });
+    }
+
+    /// Open checkpoint detail view for the selected checkpoint
+    pub fn open_checkpoint_detail(&mut self) {
+        let Some(checkpoint) = self
+            .visible_checkpoints()
+            .get(self.checkpoints_selected)
+            .copied()
+        else {
+            return;
+        };
         let instance_id = checkpoint.instance_id.clone();
         let checkpoint_id = checkpoint.checkpoint_id.clone();
 
-        let sdk = match self.create_sdk() {
-            Ok(sdk) => sdk,
-            Err(e) => {
-                self.error = Some(format!("Failed to create SDK: {}", e));
-                return;
-            }
-        };
+        self.checkpoint_detail = None;
+        self.checkpoint_tree = None;
+        self.view_mode = ViewMode::CheckpointDetail;
+        self.view_seq += 1;
+        self.servers[self.detail_server].send(Request::CheckpointDetail {
+            seq: self.view_seq,
+            instance_id,
+            checkpoint_id,
+        });
+    }
 
-        if let Err(e) = sdk.connect().await {
-            self.error = Some(format!("Connection failed: {}", e));
+    /// Mark or unmark the selected checkpoint for diffing; marking a third
+    /// drops the oldest mark
+    pub fn toggle_checkpoint_mark(&mut self) {
+        let Some(checkpoint) = self
+            .visible_checkpoints()
+            .get(self.checkpoints_selected)
+            .map(|cp| (*cp).clone())
+        else {
             return;
+        };
+        if let Some(index) = self
+            .checkpoints_marked
+            .iter()
+            .position(|cp| cp.checkpoint_id == checkpoint.checkpoint_id)
+        {
+            self.checkpoints_marked.remove(index);
+        } else {
+            if self.checkpoints_marked.len() == 2 {
+                self.checkpoints_marked.remove(0);
+            }
+            self.checkpoints_marked.push(checkpoint);
         }
+    }
 
-        match sdk.get_checkpoint(&instance_id, &checkpoint_id).await {
-            Ok(Some(checkpoint)) => {
-                self.checkpoint_detail = Some(checkpoint);
-                self.view_mode = ViewMode::CheckpointDetail;
-                self.detail_scroll = 0;
-            }
-            Ok(None) => {
-                self.error = Some("Checkpoint not found".to_string());
-            }
-            Err(e) => {
-                self.error = Some(format!("Failed to get checkpoint: {}", e));
-            }
+    /// Open the diff of the two marked checkpoints, older first
+    pub fn open_checkpoint_diff(&mut self) {
+        let [a, b] = self.checkpoints_marked.as_slice() else {
+            self.show_flash("Mark two checkpoints with Space to compare them", true);
+            return;
+        };
+        let (old, new) = if a.created_at <= b.created_at {
+            (a, b)
+        } else {
+            (b, a)
+        };
+
+        self.checkpoint_diff = Some(CheckpointDiff::new(
+            old.checkpoint_id.clone(),
+            new.checkpoint_id.clone(),
+        ));
+        self.view_mode = ViewMode::CheckpointDiff;
+        self.view_seq += 1;
+        for checkpoint in [old, new] {
+            self.servers[self.detail_server].send(Request::CheckpointDetail {
+                seq: self.view_seq,
+                instance_id: checkpoint.instance_id.clone(),
+                checkpoint_id: checkpoint.checkpoint_id.clone(),
+            });
         }
     }
 
     /// Go back to previous view
     pub fn go_back(&mut self) {
+        // Leaving a query comes before leaving the view
+        if let Some(tree) = self.detail_tree_mut() {
+            if tree.query().is_some() {
+                tree.set_query(None);
+                return;
+            }
+        }
+
+        self.view_seq += 1;
         match self.view_mode {
             ViewMode::List => {
                 // Already at top level, do nothing
@@ -528,30 +2619,46 @@ impl App {
             ViewMode::InstanceDetail => {
                 self.view_mode = ViewMode::List;
                 self.instance_detail = None;
-                self.detail_scroll = 0;
+                self.instance_tree = None;
+                self.follow = None;
             }
             ViewMode::CheckpointsList => {
                 self.view_mode = ViewMode::InstanceDetail;
                 self.checkpoints.clear();
                 self.checkpoints_total = 0;
+                self.checkpoints_offset = 0;
                 self.checkpoints_selected = 0;
+                self.checkpoints_state = LoadState::Fresh;
+                self.checkpoints_marked.clear();
             }
             ViewMode::CheckpointDetail => {
                 self.view_mode = ViewMode::CheckpointsList;
                 self.checkpoint_detail = None;
-                self.detail_scroll = 0;
+                self.checkpoint_tree = None;
+            }
+            ViewMode::CheckpointDiff => {
+                self.view_mode = ViewMode::CheckpointsList;
+                self.checkpoint_diff = None;
+            }
+            ViewMode::Timeline => {
+                self.view_mode = ViewMode::InstanceDetail;
+                self.timeline = None;
+            }
+            ViewMode::ImageMetrics => {
+                self.view_mode = ViewMode::List;
+                self.metrics_image = None;
+                self.image_metrics = None;
             }
         }
     }
 
-    /// Scroll detail view up
-    pub fn scroll_up(&mut self) {
-        self.detail_scroll = self.detail_scroll.saturating_sub(1);
-    }
-
-    /// Scroll detail view down
-    pub fn scroll_down(&mut self) {
-        self.detail_scroll = self.detail_scroll.saturating_add(1);
+    /// JSON tree of the open detail view
+    pub fn detail_tree_mut(&mut self) -> Option<&mut JsonTree> {
+        match self.view_mode {
+            ViewMode::InstanceDetail => self.instance_tree.as_mut(),
+            ViewMode::CheckpointDetail => self.checkpoint_tree.as_mut(),
+            _ => None,
+        }
     }
 
     /// Navigate in checkpoints list
@@ -570,6 +2677,45 @@ impl App {
                 .unwrap_or(self.checkpoints.len() - 1);
         }
     }
+
+    /// Navigate in the image metrics buckets
+    pub fn next_image_bucket(&mut self) {
+        let len = self.image_metrics.as_ref().map_or(0, |m| m.buckets.len());
+        if len > 0 {
+            self.image_metrics_selected = (self.image_metrics_selected + 1) % len;
+        }
+    }
+
+    /// Navigate in the image metrics buckets
+    pub fn previous_image_bucket(&mut self) {
+        let len = self.image_metrics.as_ref().map_or(0, |m| m.buckets.len());
+        if len > 0 {
+            self.image_metrics_selected = self
+                .image_metrics_selected
+                .checked_sub(1)
+                .unwrap_or(len - 1);
+        }
+    }
+}
+
+/// Show `value` in `tree`, keeping the expanded nodes of a reload
+fn show_tree(tree: &mut Option<JsonTree>, value: serde_json::Value) {
+    match tree {
+        Some(tree) => tree.set_value(value),
+        None => *tree = Some(JsonTree::new(value)),
+    }
+}
+
+/// Input and output of an instance as one object for the tree view
+fn instance_data(info: &InstanceInfo) -> serde_json::Value {
+    let mut data = serde_json::Map::new();
+    if let Some(input) = &info.input {
+        data.insert("input".to_string(), input.clone());
+    }
+    if let Some(output) = &info.output {
+        data.insert("output".to_string(), output.clone());
+    }
+    serde_json::Value::Object(data)
 }
 
 /// Format a datetime for display
@@ -595,6 +2741,23 @@ pub fn format_duration(ms: u64) -> String {
     }
 }
 
+/// Format bytes to human-readable size
+pub fn format_bytes(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = KB * 1024;
+    const GB: u64 = MB * 1024;
+
+    if bytes >= GB {
+        format!("{:.1} GB", bytes as f64 / GB as f64)
+    } else if bytes >= MB {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.1} KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
 /// Format instance status with color hint
 pub fn status_style(status: InstanceStatus) -> (&'static str, ratatui::style::Color) {
     use ratatui::style::Color;
@@ -0,0 +1,532 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Headless subcommands for scripting.
+//!
+//! Each subcommand makes the same SDK calls as the TUI over a [`Connection`]
+//! and prints the result as an aligned table, pretty JSON or NDJSON (one
+//! compact object per line), then exits.
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, Subcommand, ValueEnum};
+use runtara_management_sdk::{InstanceStatus, ListCheckpointsOptions, ListImagesOptions};
+use serde::Serialize;
+use std::future::Future;
+use std::io::{self, Write};
+
+use crate::app::{format_bytes, format_datetime, format_duration, status_style, PAGE_SIZE};
+use crate::config::Settings;
+use crate::connection::{self, Connection, ConnectionState};
+use crate::filter::InstanceFilter;
+use crate::worker::{call, instances_options};
+
+/// Subcommands; without one the TUI starts.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// List or show instances
+    #[command(subcommand)]
+    Instances(InstancesCommand),
+    /// List the checkpoints of an instance
+    #[command(subcommand)]
+    Checkpoints(CheckpointsCommand),
+    /// Show one checkpoint
+    #[command(subcommand)]
+    Checkpoint(CheckpointCommand),
+    /// List images
+    #[command(subcommand)]
+    Images(ImagesCommand),
+    /// Show server health
+    Health(OutputArgs),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum InstancesCommand {
+    /// List instances, newest first
+    List {
+        /// Only instances with this status
+        #[arg(long, value_enum)]
+        status: Option<StatusArg>,
+        /// Filter bar terms, e.g. "image:etl created>1d"
+        #[arg(long, value_parser = parse_filter)]
+        filter: Option<InstanceFilter>,
+        #[command(flatten)]
+        page: PageArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Show the details of one instance
+    Get {
+        instance_id: String,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CheckpointsCommand {
+    /// List the checkpoints of an instance
+    List {
+        instance_id: String,
+        #[command(flatten)]
+        page: PageArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CheckpointCommand {
+    /// Show the data of one checkpoint
+    Get {
+        instance_id: String,
+        checkpoint_id: String,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ImagesCommand {
+    /// List images
+    List {
+        #[command(flatten)]
+        page: PageArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+}
+
+/// Instance status accepted by `--status`.
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum StatusArg {
+    Pending,
+    Running,
+    Suspended,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl From<StatusArg> for InstanceStatus {
+    fn from(status: StatusArg) -> Self {
+        match status {
+            StatusArg::Pending => InstanceStatus::Pending,
+            StatusArg::Running => InstanceStatus::Running,
+            StatusArg::Suspended => InstanceStatus::Suspended,
+            StatusArg::Completed => InstanceStatus::Completed,
+            StatusArg::Failed => InstanceStatus::Failed,
+            StatusArg::Cancelled => InstanceStatus::Cancelled,
+        }
+    }
+}
+
+/// Paging options of list subcommands.
+#[derive(Args, Debug)]
+pub struct PageArgs {
+    /// Maximum number of items
+    #[arg(long, default_value_t = PAGE_SIZE)]
+    limit: u32,
+    /// Number of items to skip
+    #[arg(long, default_value_t = 0)]
+    offset: u32,
+    /// Fetch every page (`--limit` sets the page size)
+    #[arg(long)]
+    all: bool,
+}
+
+/// Output format options.
+#[derive(Args, Debug)]
+pub struct OutputArgs {
+    /// Print pretty JSON
+    #[arg(long, conflicts_with = "ndjson")]
+    json: bool,
+    /// Print one compact JSON object per line
+    #[arg(long)]
+    ndjson: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Table,
+    Json,
+    Ndjson,
+}
+
+impl OutputArgs {
+    fn format(&self) -> Format {
+        if self.json {
+            Format::Json
+        } else if self.ndjson {
+            Format::Ndjson
+        } else {
+            Format::Table
+        }
+    }
+}
+
+fn parse_filter(text: &str) -> Result<InstanceFilter, String> {
+    InstanceFilter::default().parse_bar(text)
+}
+
+/// Connect with `settings`, run `command` and print its output.
+pub async fn run(command: Command, settings: &Settings) -> Result<()> {
+    if settings.skip_cert_verification {
+        eprintln!(
+            "warning: TLS certificate verification is disabled for {}",
+            settings.server
+        );
+    }
+
+    let (config, addrs) = connection::configure(settings).await?;
+    let (connection, supervisor) = Connection::start(config, &addrs)?;
+    let result = match wait_connected(&connection).await {
+        Ok(()) => execute(command, &connection, settings).await,
+        Err(e) => Err(e).with_context(|| format!("Failed to connect to {}", settings.server)),
+    };
+    supervisor.abort();
+
+    match result {
+        // The reader went away (e.g. `| head`); not an error for scripts
+        Err(e) if is_broken_pipe(&e) => Ok(()),
+        result => result,
+    }
+}
+
+/// Wait for the first connection attempt to finish
+async fn wait_connected(connection: &Connection) -> Result<()> {
+    let mut state = connection.subscribe();
+    let state = state
+        .wait_for(|s| !matches!(s, ConnectionState::Connecting { .. }))
+        .await
+        .map(|s| s.clone())
+        .context("Connection closed")?;
+    match state {
+        ConnectionState::Waiting { error, .. } => bail!(error),
+        _ => Ok(()),
+    }
+}
+
+fn is_broken_pipe(e: &anyhow::Error) -> bool {
+    e.downcast_ref::<io::Error>()
+        .is_some_and(|e| e.kind() == io::ErrorKind::BrokenPipe)
+}
+
+async fn execute(command: Command, connection: &Connection, settings: &Settings) -> Result<()> {
+    let tenant_id = settings.tenant.clone();
+
+    match command {
+        Command::Instances(InstancesCommand::List {
+            status,
+            filter,
+            page,
+            output,
+        }) => {
+            let filter = filter.unwrap_or_default();
+            let status = status.map(InstanceStatus::from);
+            let (instances, total) = fetch_pages(&page, |limit, offset| {
+                let options = instances_options(tenant_id.clone(), status, &filter, limit, offset);
+                async move {
+                    let result = call(connection, |sdk| sdk.list_instances(options))
+                        .await
+                        .context("Failed to list instances")?;
+                    Ok((result.instances, result.total_count))
+                }
+            })
+            .await?;
+
+            print_list(
+                output.format(),
+                &instances,
+                &["ID", "STATUS", "IMAGE", "TENANT", "CREATED", "FINISHED"],
+                |inst| {
+                    vec![
+                        inst.instance_id.clone(),
+                        status_style(inst.status).0.to_string(),
+                        inst.image_id.clone(),
+                        inst.tenant_id.clone(),
+                        format_datetime(&inst.created_at),
+                        inst.finished_at
+                            .as_ref()
+                            .map(format_datetime)
+                            .unwrap_or_else(|| "-".to_string()),
+                    ]
+                },
+            )?;
+            report_more(&page, instances.len(), total, "instances");
+        }
+        Command::Instances(InstancesCommand::Get {
+            instance_id,
+            output,
+        }) => {
+            let info = call(connection, |sdk| sdk.get_instance_status(&instance_id))
+                .await
+                .context("Failed to get instance details")?;
+
+            print_item(output.format(), &info, |out| {
+                let duration = match (info.started_at, info.finished_at) {
+                    (Some(start), Some(end)) => {
+                        format_duration((end - start).num_milliseconds().max(0) as u64)
+                    }
+                    _ => "-".to_string(),
+                };
+                print_fields(
+                    out,
+                    &[
+                        ("Instance", info.instance_id.clone()),
+                        ("Status", status_style(info.status).0.to_string()),
+                        ("Image", format!("{} ({})", info.image_name, info.image_id)),
+                        ("Tenant", info.tenant_id.clone()),
+                        ("Created", format_datetime(&info.created_at)),
+                        ("Started", optional_time(info.started_at.as_ref())),
+                        ("Finished", optional_time(info.finished_at.as_ref())),
+                        ("Duration", duration),
+                        ("Heartbeat", optional_time(info.heartbeat_at.as_ref())),
+                        (
+                            "Retries",
+                            format!("{}/{}", info.retry_count, info.max_retries),
+                        ),
+                        (
+                            "Checkpoint",
+                            info.checkpoint_id.clone().unwrap_or_else(|| "-".into()),
+                        ),
+                    ],
+                )?;
+                if let Some(ref error) = info.error {
+                    writeln!(out, "\nError:\n{}", error)?;
+                }
+                if let Some(ref input) = info.input {
+                    writeln!(out, "\nInput:\n{}", serde_json::to_string_pretty(input)?)?;
+                }
+                if let Some(ref value) = info.output {
+                    writeln!(out, "\nOutput:\n{}", serde_json::to_string_pretty(value)?)?;
+                }
+                Ok(())
+            })?;
+        }
+        Command::Checkpoints(CheckpointsCommand::List {
+            instance_id,
+            page,
+            output,
+        }) => {
+            let (checkpoints, total) = fetch_pages(&page, |limit, offset| {
+                let options = ListCheckpointsOptions::new()
+                    .with_limit(limit)
+                    .with_offset(offset);
+                let instance_id = &instance_id;
+                async move {
+                    let result = call(connection, |sdk| sdk.list_checkpoints(instance_id, options))
+                        .await
+                        .context("Failed to list checkpoints")?;
+                    Ok((result.checkpoints, result.total_count))
+                }
+            })
+            .await?;
+
+            print_list(
+                output.format(),
+                &checkpoints,
+                &["ID", "CREATED", "SIZE"],
+                |cp| {
+                    vec![
+                        cp.checkpoint_id.clone(),
+                        format_datetime(&cp.created_at),
+                        format_bytes(cp.data_size_bytes),
+                    ]
+                },
+            )?;
+            report_more(&page, checkpoints.len(), total, "checkpoints");
+        }
+        Command::Checkpoint(CheckpointCommand::Get {
+            instance_id,
+            checkpoint_id,
+            output,
+        }) => {
+            let checkpoint = call(connection, |sdk| {
+                sdk.get_checkpoint(&instance_id, &checkpoint_id)
+            })
+            .await
+            .context("Failed to get checkpoint")?
+            .context("Checkpoint not found")?;
+
+            print_item(output.format(), &checkpoint, |out| {
+                print_fields(
+                    out,
+                    &[
+                        ("Checkpoint", checkpoint.checkpoint_id.clone()),
+                        ("Instance", checkpoint.instance_id.clone()),
+                        ("Created", format_datetime(&checkpoint.created_at)),
+                    ],
+                )?;
+                writeln!(
+                    out,
+                    "\nData:\n{}",
+                    serde_json::to_string_pretty(&checkpoint.data)?
+                )?;
+                Ok(())
+            })?;
+        }
+        Command::Images(ImagesCommand::List { page, output }) => {
+            let (images, total) = fetch_pages(&page, |limit, offset| {
+                let options = ListImagesOptions {
+                    tenant_id: tenant_id.clone(),
+                    limit,
+                    offset,
+                };
+                async move {
+                    let result = call(connection, |sdk| sdk.list_images(options))
+                        .await
+                        .context("Failed to list images")?;
+                    Ok((result.images, result.total_count))
+                }
+            })
+            .await?;
+
+            print_list(
+                output.format(),
+                &images,
+                &["ID", "NAME", "TENANT", "RUNNER", "CREATED"],
+                |img| {
+                    vec![
+                        img.image_id.clone(),
+                        img.name.clone(),
+                        img.tenant_id.clone(),
+                        format!("{:?}", img.runner_type),
+                        format_datetime(&img.created_at),
+                    ]
+                },
+            )?;
+            report_more(&page, images.len(), total, "images");
+        }
+        Command::Health(output) => {
+            let health = call(connection, |sdk| sdk.health_check())
+                .await
+                .context("Failed to get health")?;
+
+            print_item(output.format(), &health, |out| {
+                print_fields(
+                    out,
+                    &[
+                        (
+                            "Healthy",
+                            if health.healthy { "yes" } else { "no" }.to_string(),
+                        ),
+                        ("Version", health.version.clone()),
+                        ("Uptime", format_duration(health.uptime_ms.max(0) as u64)),
+                        ("Active", health.active_instances.to_string()),
+                    ],
+                )
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Fetch one page, or every page from `--offset` on with `--all`.
+///
+/// `fetch` gets the limit and offset of a page and returns its items and the
+/// total count.
+async fn fetch_pages<T, F, Fut>(page: &PageArgs, mut fetch: F) -> Result<(Vec<T>, u32)>
+where
+    F: FnMut(u32, u32) -> Fut,
+    Fut: Future<Output = Result<(Vec<T>, u32)>>,
+{
+    let limit = page.limit.max(1);
+    let mut items = Vec::new();
+    let mut offset = page.offset;
+    loop {
+        let (batch, total) = fetch(limit, offset).await?;
+        let fetched = batch.len() as u32;
+        items.extend(batch);
+        offset += fetched;
+        if !page.all || fetched == 0 || offset >= total {
+            return Ok((items, total));
+        }
+    }
+}
+
+/// Tell the user on stderr when the list was cut short
+fn report_more(page: &PageArgs, shown: usize, total: u32, what: &str) {
+    let end = page.offset + shown as u32;
+    if end < total {
+        eprintln!(
+            "Showing {}-{} of {} {}; use --offset or --all for more",
+            page.offset + 1,
+            end,
+            total,
+            what
+        );
+    }
+}
+
+/// Print a list as a table, a JSON array or one JSON object per line
+fn print_list<T: Serialize>(
+    format: Format,
+    items: &[T],
+    headers: &[&str],
+    row: impl Fn(&T) -> Vec<String>,
+) -> Result<()> {
+    let mut out = io::stdout().lock();
+    match format {
+        Format::Json => writeln!(out, "{}", serde_json::to_string_pretty(items)?)?,
+        Format::Ndjson => {
+            for item in items {
+                writeln!(out, "{}", serde_json::to_string(item)?)?;
+            }
+        }
+        Format::Table => {
+            let rows: Vec<Vec<String>> = items.iter().map(row).collect();
+            let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+            for row in &rows {
+                for (width, cell) in widths.iter_mut().zip(row) {
+                    *width = (*width).max(cell.chars().count());
+                }
+            }
+
+            let headers: Vec<String> = headers.iter().map(|h| h.to_string()).collect();
+            for row in std::iter::once(&headers).chain(&rows) {
+                let line: Vec<String> = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, &width)| format!("{:<width$}", cell))
+                    .collect();
+                writeln!(out, "{}", line.join("  ").trim_end())?;
+            }
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Print one item as JSON, or in table format through `table`
+fn print_item<T: Serialize>(
+    format: Format,
+    item: &T,
+    table: impl FnOnce(&mut dyn Write) -> Result<()>,
+) -> Result<()> {
+    let mut out = io::stdout().lock();
+    match format {
+        Format::Json => writeln!(out, "{}", serde_json::to_string_pretty(item)?)?,
+        Format::Ndjson => writeln!(out, "{}", serde_json::to_string(item)?)?,
+        Format::Table => table(&mut out)?,
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Print aligned `label: value` lines
+fn print_fields(out: &mut dyn Write, fields: &[(&str, String)]) -> Result<()> {
+    let width = fields
+        .iter()
+        .map(|(label, _)| label.len())
+        .max()
+        .unwrap_or(0)
+        + 1;
+    for (label, value) in fields {
+        writeln!(out, "{:<width$} {}", format!("{}:", label), value)?;
+    }
+    Ok(())
+}
+
+fn optional_time(time: Option<&chrono::DateTime<chrono::Utc>>) -> String {
+    time.map(format_datetime).unwrap_or_else(|| "-".to_string())
+}
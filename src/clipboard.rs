@@ -0,0 +1,17 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! System clipboard access through the terminal.
+//!
+//! Text is sent with the OSC 52 escape sequence, which the terminal emulator
+//! puts on the clipboard of the machine it runs on, so copying works over SSH
+//! too. tmux forwards the sequence with `set -g set-clipboard on`.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use std::io::{self, Write};
+
+/// Put `text` on the system clipboard
+pub fn copy(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    write!(stdout, "\x1b]52;c;{}\x07", STANDARD.encode(text))?;
+    stdout.flush()
+}
@@ -0,0 +1,50 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Side-by-side metrics of several tenants.
+//!
+//! The metrics of each compared tenant are fetched over the same range and
+//! granularity, then summed over their buckets into one row per tenant.
+
+use runtara_management_sdk::TenantMetricsResult;
+
+/// Metrics of one tenant summed over all buckets.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TenantTotals {
+    pub invocations: i64,
+    pub success: i64,
+    pub failed: i64,
+    pub cancelled: i64,
+    /// Average run time, weighted by the invocations of each bucket
+    pub avg_duration_seconds: Option<f64>,
+    /// Longest run time of any bucket
+    pub max_duration_seconds: Option<f64>,
+}
+
+impl TenantTotals {
+    pub fn new(metrics: &TenantMetricsResult) -> Self {
+        let mut totals = Self::default();
+        let mut weighted_duration = 0.0;
+        let mut timed = 0;
+        for bucket in &metrics.buckets {
+            totals.invocations += bucket.invocation_count;
+            totals.success += bucket.success_count;
+            totals.failed += bucket.failure_count;
+            totals.cancelled += bucket.cancelled_count;
+            if let Some(avg) = bucket.avg_duration_seconds {
+                weighted_duration += avg * bucket.invocation_count as f64;
+                timed += bucket.invocation_count;
+            }
+            if let Some(max) = bucket.max_duration_seconds {
+                totals.max_duration_seconds =
+                    Some(totals.max_duration_seconds.map_or(max, |m: f64| m.max(max)));
+            }
+        }
+        totals.avg_duration_seconds = (timed > 0).then(|| weighted_duration / timed as f64);
+        totals
+    }
+
+    /// Successful share of all invocations, in percent
+    pub fn success_rate(&self) -> Option<f64> {
+        (self.invocations > 0).then(|| self.success as f64 * 100.0 / self.invocations as f64)
+    }
+}
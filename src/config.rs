@@ -0,0 +1,213 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Configuration file with named connection profiles.
+//!
+//! The file lives at `$XDG_CONFIG_HOME/runtara-tui/config.toml` by default:
+//!
+//! ```toml
+//! default_profile = "dev"
+//!
+//! [profiles.dev]
+//! server = "127.0.0.1:8002"
+//! skip_cert_verification = true
+//!
+//! [profiles.production]
+//! server = "10.0.0.5:8002"
+//! server_name = "runtara.example.com"
+//! tenant = "acme"
+//! refresh = 10
+//! stale_after = 300
+//! ```
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Server address used when neither the command line nor the profile sets one.
+pub const DEFAULT_SERVER: &str = "127.0.0.1:8002";
+
+/// Refresh interval in seconds used when none is configured.
+pub const DEFAULT_REFRESH_SECS: u64 = 5;
+
+/// Heartbeat age in seconds after which a running instance is stale, when
+/// none is configured.
+pub const DEFAULT_STALE_AFTER_SECS: u64 = 120;
+
+/// Contents of the configuration file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Profile used when `--profile` is not given
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// Connection settings of one environment.
+///
+/// Every field is optional; unset fields fall back to the defaults. The
+/// command line options are parsed into a `Profile` too, which is layered on
+/// top of the selected one.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// Server address
+    pub server: Option<String>,
+    /// Expected name in the server certificate
+    pub server_name: Option<String>,
+    /// Skip TLS certificate verification
+    pub skip_cert_verification: Option<bool>,
+    /// Default tenant
+    pub tenant: Option<String>,
+    /// Refresh interval in seconds
+    pub refresh: Option<u64>,
+    /// Heartbeat age in seconds after which a running instance is stale
+    pub stale_after: Option<u64>,
+}
+
+impl Config {
+    /// Default location of the configuration file
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("runtara-tui").join("config.toml"))
+    }
+
+    /// Load the configuration file.
+    ///
+    /// A missing file at the default location yields an empty configuration;
+    /// an explicitly given `path` must exist.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let (path, required) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match Self::default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Self::default()),
+            },
+        };
+
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && !required => {
+                return Ok(Self::default())
+            }
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+
+        toml::from_str(&text).with_context(|| format!("Invalid config file {}", path.display()))
+    }
+
+    /// Profile names in display order
+    pub fn profile_names(&self) -> Vec<String> {
+        self.profiles.keys().cloned().collect()
+    }
+
+    /// Resolve the settings for `name` (or the default profile), with
+    /// `overrides` taking precedence over the profile
+    pub fn settings(&self, name: Option<&str>, overrides: &Profile) -> Result<Settings> {
+        let name = name.or(self.default_profile.as_deref());
+        let profile = match name {
+            Some(name) => match self.profiles.get(name) {
+                Some(profile) => profile.clone(),
+                None if self.profiles.is_empty() => {
+                    bail!("Unknown profile '{}': no profiles are configured", name)
+                }
+                None => bail!(
+                    "Unknown profile '{}' (available: {})",
+                    name,
+                    self.profile_names().join(", ")
+                ),
+            },
+            None => Profile::default(),
+        };
+
+        Ok(Settings::new(
+            name.map(str::to_string),
+            overrides.clone().or(profile),
+        ))
+    }
+
+    /// Resolve the settings of every monitored server.
+    ///
+    /// Several profiles give one server each; each keeps its own address
+    /// and server name, so `servers` and a server name override are
+    /// rejected. Otherwise the single (or default) profile is used, once per
+    /// address in `servers` if several are given.
+    pub fn server_settings(
+        &self,
+        profiles: &[String],
+        servers: &[String],
+        overrides: &Profile,
+    ) -> Result<Vec<Settings>> {
+        if profiles.len() > 1 {
+            if !servers.is_empty() || overrides.server_name.is_some() {
+                bail!("--server and --server-name cannot be combined with several profiles");
+            }
+            return profiles
+                .iter()
+                .map(|name| self.settings(Some(name), overrides))
+                .collect();
+        }
+
+        let settings = self.settings(profiles.first().map(String::as_str), overrides)?;
+        if servers.len() > 1 {
+            Ok(servers
+                .iter()
+                .map(|server| Settings {
+                    server: server.clone(),
+                    ..settings.clone()
+                })
+                .collect())
+        } else {
+            Ok(vec![settings])
+        }
+    }
+}
+
+impl Profile {
+    /// Fill unset fields from `base`
+    pub fn or(self, base: Profile) -> Profile {
+        Profile {
+            server: self.server.or(base.server),
+            server_name: self.server_name.or(base.server_name),
+            skip_cert_verification: self.skip_cert_verification.or(base.skip_cert_verification),
+            tenant: self.tenant.or(base.tenant),
+            refresh: self.refresh.or(base.refresh),
+            stale_after: self.stale_after.or(base.stale_after),
+        }
+    }
+}
+
+/// Fully resolved connection settings.
+#[derive(Debug, Clone)]
+pub struct Settings {
+    /// Name of the profile the settings came from
+    pub profile: Option<String>,
+    pub server: String,
+    pub server_name: Option<String>,
+    pub skip_cert_verification: bool,
+    pub tenant: Option<String>,
+    pub refresh_interval: Duration,
+    pub stale_after: Duration,
+}
+
+impl Settings {
+    fn new(profile: Option<String>, values: Profile) -> Self {
+        Self {
+            profile,
+            server: values.server.unwrap_or_else(|| DEFAULT_SERVER.to_string()),
+            server_name: values.server_name,
+            skip_cert_verification: values.skip_cert_verification.unwrap_or(false),
+            tenant: values.tenant,
+            refresh_interval: Duration::from_secs(
+                values.refresh.unwrap_or(DEFAULT_REFRESH_SECS).max(1),
+            ),
+            stale_after: Duration::from_secs(
+                values
+                    .stale_after
+                    .unwrap_or(DEFAULT_STALE_AFTER_SECS)
+                    .max(1),
+            ),
+        }
+    }
+}
@@ -0,0 +1,257 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Long-lived connection to runtara-environment.
+//!
+//! One connection is shared by all requests. A supervisor task watches it and
+//! reconnects with exponential backoff whenever it drops. When the server name
+//! resolves to several addresses, each is tried in turn.
+
+use anyhow::{bail, Context};
+use runtara_management_sdk::{ManagementSdk, SdkConfig, SdkError};
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{watch, Notify};
+use tokio::task::JoinHandle;
+
+use crate::config::Settings;
+
+/// Maximum time a single request may take.
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delay before the first reconnect attempt.
+const BACKOFF_INITIAL: Duration = Duration::from_secs(1);
+
+/// Upper bound for the reconnect delay.
+const BACKOFF_MAX: Duration = Duration::from_secs(30);
+
+/// How often the supervisor checks a healthy connection.
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// State of the connection to the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// Connection attempt in progress (attempt 0 is the initial connect)
+    Connecting { attempt: u32 },
+    /// Connected to `addr` and usable
+    Connected { addr: SocketAddr },
+    /// Last attempt failed; the next one is scheduled at `retry_at`
+    Waiting {
+        attempt: u32,
+        retry_at: Instant,
+        error: String,
+    },
+    /// The server could not be set up (e.g. its address does not resolve);
+    /// never retried
+    Failed { error: String },
+}
+
+/// Server address resolved from `HOST:PORT`.
+#[derive(Debug, Clone)]
+pub struct ResolvedServer {
+    /// Host as given, without brackets for IPv6 literals
+    pub host: String,
+    /// All addresses the host resolved to, in resolver order
+    pub addrs: Vec<SocketAddr>,
+}
+
+/// Resolve a `HOST:PORT` server address.
+///
+/// Fails when the address is malformed or the host does not resolve.
+pub async fn resolve(server: &str) -> anyhow::Result<ResolvedServer> {
+    let (host, port) = server
+        .rsplit_once(':')
+        .with_context(|| format!("Invalid server address '{}': expected HOST:PORT", server))?;
+    let port: u16 = port
+        .parse()
+        .with_context(|| format!("Invalid port in server address '{}'", server))?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if host.is_empty() {
+        bail!("Invalid server address '{}': missing host", server);
+    }
+
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .with_context(|| format!("Failed to resolve '{}'", host))?
+        .collect();
+    if addrs.is_empty() {
+        bail!("'{}' did not resolve to any address", host);
+    }
+
+    Ok(ResolvedServer {
+        host: host.to_string(),
+        addrs,
+    })
+}
+
+/// Build the SDK configuration for `settings`, resolving the server.
+///
+/// Returns the configuration and every address to try.
+pub async fn configure(settings: &Settings) -> anyhow::Result<(SdkConfig, Vec<SocketAddr>)> {
+    let resolved = resolve(&settings.server).await?;
+
+    let config = SdkConfig {
+        // Replaced by each resolved address in turn
+        server_addr: resolved.addrs[0],
+        server_name: settings
+            .server_name
+            .clone()
+            .unwrap_or_else(|| resolved.host.clone()),
+        skip_cert_verification: settings.skip_cert_verification,
+        connect_timeout: Duration::from_secs(5),
+        // Also the QUIC idle timeout, so it must exceed the keep-alive interval
+        request_timeout: Duration::from_secs(30),
+    };
+
+    Ok((config, resolved.addrs))
+}
+
+/// Shared connection handle.
+pub struct Connection {
+    /// One client per resolved address
+    sdks: Vec<(SocketAddr, ManagementSdk)>,
+    state: watch::Sender<ConnectionState>,
+    /// Wakes the supervisor early after a request hit a connection error
+    wake: Notify,
+}
+
+impl Connection {
+    /// Create the connection and start its supervisor.
+    ///
+    /// `config.server_addr` is replaced by each of `addrs` in turn. The
+    /// supervisor stops when the returned handle is aborted.
+    pub fn start(
+        config: SdkConfig,
+        addrs: &[SocketAddr],
+    ) -> Result<(Arc<Self>, JoinHandle<()>), SdkError> {
+        let sdks = addrs
+            .iter()
+            .map(|&addr| {
+                let config = SdkConfig {
+                    server_addr: addr,
+                    ..config.clone()
+                };
+                ManagementSdk::new(config).map(|sdk| (addr, sdk))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let (state, _) = watch::channel(ConnectionState::Connecting { attempt: 0 });
+        let connection = Arc::new(Self {
+            sdks,
+            state,
+            wake: Notify::new(),
+        });
+        let supervisor = tokio::spawn(supervise(connection.clone()));
+        Ok((connection, supervisor))
+    }
+
+    /// Subscribe to connection state changes
+    pub fn subscribe(&self) -> watch::Receiver<ConnectionState> {
+        self.state.subscribe()
+    }
+
+    /// Get the SDK once the connection is usable.
+    ///
+    /// Waits for an in-progress connection attempt; fails immediately while
+    /// waiting for the next reconnect attempt.
+    pub async fn sdk(&self) -> Result<&ManagementSdk, String> {
+        let mut state = self.state.subscribe();
+        let current = state
+            .wait_for(|s| !matches!(s, ConnectionState::Connecting { .. }))
+            .await
+            .map(|s| s.clone())
+            .map_err(|_| "Connection closed".to_string())?;
+
+        match current {
+            ConnectionState::Connected { addr } => self
+                .sdks
+                .iter()
+                .find(|(a, _)| *a == addr)
+                .map(|(_, sdk)| sdk)
+                .ok_or_else(|| "Connection closed".to_string()),
+            ConnectionState::Waiting {
+                retry_at, error, ..
+            } => Err(format!(
+                "Not connected (retrying in {}s): {}",
+                retry_at.saturating_duration_since(Instant::now()).as_secs(),
+                error
+            )),
+            ConnectionState::Failed { error } => Err(error),
+            ConnectionState::Connecting { .. } => unreachable!(),
+        }
+    }
+
+    /// Run an SDK call with the request timeout.
+    ///
+    /// Connection failures wake the supervisor so it can reconnect right away.
+    pub async fn call<T>(
+        &self,
+        request: impl Future<Output = Result<T, SdkError>>,
+    ) -> Result<T, SdkError> {
+        let result = match tokio::time::timeout(REQUEST_TIMEOUT, request).await {
+            Ok(result) => result,
+            Err(_) => Err(SdkError::Timeout(REQUEST_TIMEOUT.as_millis() as u64)),
+        };
+        if matches!(result, Err(SdkError::Connection(_) | SdkError::Timeout(_))) {
+            self.wake.notify_one();
+        }
+        result
+    }
+}
+
+/// Delay before reconnect attempt number `attempt` (1-based)
+fn backoff(attempt: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+    BACKOFF_INITIAL.saturating_mul(factor).min(BACKOFF_MAX)
+}
+
+/// Keep the connection alive, reconnecting with exponential backoff
+async fn supervise(connection: Arc<Connection>) {
+    let mut attempt = 0;
+    // Index of the address that last connected; tried first on reconnect
+    let mut current = 0;
+    loop {
+        if connection.sdks[current].1.is_connected().await {
+            tokio::select! {
+                _ = connection.wake.notified() => {}
+                _ = tokio::time::sleep(CHECK_INTERVAL) => {}
+            }
+            continue;
+        }
+
+        connection
+            .state
+            .send_replace(ConnectionState::Connecting { attempt });
+
+        let mut errors = Vec::new();
+        let count = connection.sdks.len();
+        for i in (0..count).map(|n| (current + n) % count) {
+            let (addr, ref sdk) = connection.sdks[i];
+            match sdk.connect().await {
+                Ok(()) => {
+                    current = i;
+                    break;
+                }
+                Err(e) if count == 1 => errors.push(e.to_string()),
+                Err(e) => errors.push(format!("{}: {}", addr, e)),
+            }
+        }
+
+        if errors.len() < count {
+            attempt = 0;
+            let addr = connection.sdks[current].0;
+            connection
+                .state
+                .send_replace(ConnectionState::Connected { addr });
+        } else {
+            attempt += 1;
+            let delay = backoff(attempt);
+            connection.state.send_replace(ConnectionState::Waiting {
+                attempt,
+                retry_at: Instant::now() + delay,
+                error: errors.join("; "),
+            });
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
@@ -0,0 +1,286 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Structural diff of checkpoint data.
+//!
+//! Objects are compared by key and arrays by index; every differing leaf or
+//! subtree becomes one [`Change`], addressed by a jq-style path.
+
+use runtara_management_sdk::Checkpoint;
+use serde_json::Value;
+
+use crate::app::Viewport;
+
+/// One difference between the old and the new value.
+#[derive(Debug, Clone)]
+pub struct Change {
+    /// jq-style path, e.g. `.order.items[2].qty`
+    pub path: String,
+    pub kind: ChangeKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum ChangeKind {
+    Added(Value),
+    Removed(Value),
+    Changed { old: Value, new: Value },
+}
+
+/// Differences from `old` to `new`, in document order
+pub fn diff(old: &Value, new: &Value) -> Vec<Change> {
+    let mut changes = Vec::new();
+    diff_into(old, new, String::new(), &mut changes);
+    changes
+}
+
+fn diff_into(old: &Value, new: &Value, path: String, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            for (key, old_child) in old_map {
+                let child_path = child_path(&path, &key_segment(key));
+                match new_map.get(key) {
+                    Some(new_child) => diff_into(old_child, new_child, child_path, changes),
+                    None => changes.push(Change {
+                        path: child_path,
+                        kind: ChangeKind::Removed(old_child.clone()),
+                    }),
+                }
+            }
+            for (key, new_child) in new_map {
+                if !old_map.contains_key(key) {
+                    changes.push(Change {
+                        path: child_path(&path, &key_segment(key)),
+                        kind: ChangeKind::Added(new_child.clone()),
+                    });
+                }
+            }
+        }
+        (Value::Array(old_items), Value::Array(new_items)) => {
+            for index in 0..old_items.len().max(new_items.len()) {
+                let child_path = child_path(&path, &format!("[{}]", index));
+                match (old_items.get(index), new_items.get(index)) {
+                    (Some(o), Some(n)) => diff_into(o, n, child_path, changes),
+                    (Some(o), None) => changes.push(Change {
+                        path: child_path,
+                        kind: ChangeKind::Removed(o.clone()),
+                    }),
+                    (None, Some(n)) => changes.push(Change {
+                        path: child_path,
+                        kind: ChangeKind::Added(n.clone()),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if old != new => changes.push(Change {
+            path: if path.is_empty() {
+                ".".to_string()
+            } else {
+                path
+            },
+            kind: ChangeKind::Changed {
+                old: old.clone(),
+                new: new.clone(),
+            },
+        }),
+        _ => {}
+    }
+}
+
+/// Compact JSON of `value`, cut to `max_chars` characters
+pub fn preview(value: &Value, max_chars: usize) -> String {
+    let text = value.to_string();
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text,
+    }
+}
+
+/// `path` extended by `segment`; a bracket at the root needs a leading `.`
+/// to stay a jq path, e.g. `.[0]`
+fn child_path(path: &str, segment: &str) -> String {
+    if path.is_empty() && segment.starts_with('[') {
+        format!(".{}", segment)
+    } else {
+        format!("{}{}", path, segment)
+    }
+}
+
+/// `.key` for identifiers, `["key"]` otherwise
+fn key_segment(key: &str) -> String {
+    let is_identifier = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if is_identifier {
+        format!(".{}", key)
+    } else {
+        format!("[{}]", Value::String(key.to_string()))
+    }
+}
+
+/// Diff view of two checkpoints of one instance.
+#[derive(Debug)]
+pub struct CheckpointDiff {
+    /// Older checkpoint, shown on the left / as removals
+    pub old_id: String,
+    /// Newer checkpoint, shown on the right / as additions
+    pub new_id: String,
+    pub old: Option<Checkpoint>,
+    pub new: Option<Checkpoint>,
+    /// Computed once both checkpoints are loaded
+    pub changes: Vec<Change>,
+    /// Side-by-side columns instead of unified lines
+    pub side_by_side: bool,
+    selected: usize,
+    view: Viewport,
+}
+
+impl CheckpointDiff {
+    pub fn new(old_id: String, new_id: String) -> Self {
+        Self {
+            old_id,
+            new_id,
+            old: None,
+            new: None,
+            changes: Vec::new(),
+            side_by_side: false,
+            selected: 0,
+            view: Viewport::default(),
+        }
+    }
+
+    /// Store a loaded checkpoint; others than the two compared are ignored
+    pub fn receive(&mut self, checkpoint: Checkpoint) {
+        if checkpoint.checkpoint_id == self.old_id {
+            self.old = Some(checkpoint);
+        } else if checkpoint.checkpoint_id == self.new_id {
+            self.new = Some(checkpoint);
+        } else {
+            return;
+        }
+        if let (Some(old), Some(new)) = (&self.old, &self.new) {
+            self.changes = diff(&old.data, &new.data);
+        }
+    }
+
+    /// Whether both checkpoints have arrived
+    pub fn is_loaded(&self) -> bool {
+        self.old.is_some() && self.new.is_some()
+    }
+
+    /// Number of added, removed and changed paths
+    pub fn counts(&self) -> (usize, usize, usize) {
+        self.changes
+            .iter()
+            .fold((0, 0, 0), |(a, r, c), change| match change.kind {
+                ChangeKind::Added(_) => (a + 1, r, c),
+                ChangeKind::Removed(_) => (a, r + 1, c),
+                ChangeKind::Changed { .. } => (a, r, c + 1),
+            })
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Changes to draw in a view `height` rows high, scrolled so the cursor
+    /// is visible
+    pub fn visible_changes(&self, height: usize) -> (usize, &[Change]) {
+        let offset = self.view.scroll(self.selected, self.changes.len(), height);
+        let end = (offset + height).min(self.changes.len());
+        (offset, &self.changes[offset.min(end)..end])
+    }
+
+    pub fn toggle_layout(&mut self) {
+        self.side_by_side = !self.side_by_side;
+    }
+
+    pub fn next(&mut self) {
+        self.select(self.selected + 1);
+    }
+
+    pub fn previous(&mut self) {
+        self.select(self.selected.saturating_sub(1));
+    }
+
+    pub fn page_down(&mut self) {
+        self.select(self.selected + self.view.page());
+    }
+
+    pub fn page_up(&mut self) {
+        self.select(self.selected.saturating_sub(self.view.page()));
+    }
+
+    pub fn first(&mut self) {
+        self.select(0);
+    }
+
+    pub fn last(&mut self) {
+        self.select(self.changes.len().saturating_sub(1));
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.changes.len().saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// `+path`, `-path` or `~path` for each change
+    fn summary(old: Value, new: Value) -> Vec<String> {
+        diff(&old, &new)
+            .into_iter()
+            .map(|change| {
+                let sign = match change.kind {
+                    ChangeKind::Added(_) => '+',
+                    ChangeKind::Removed(_) => '-',
+                    ChangeKind::Changed { .. } => '~',
+                };
+                format!("{}{}", sign, change.path)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn equal_values_have_no_changes() {
+        let value = json!({"a": [1, {"b": null}], "c": "x"});
+        assert!(diff(&value, &value.clone()).is_empty());
+    }
+
+    #[test]
+    fn compares_objects_by_key() {
+        assert_eq!(
+            summary(
+                json!({"a": 1, "b": {"c": true}, "gone": 0}),
+                json!({"a": 2, "b": {"c": true}, "new": 0}),
+            ),
+            ["~.a", "-.gone", "+.new"]
+        );
+        assert_eq!(
+            summary(json!({"a b": 1}), json!({"a b": 2})),
+            ["~.[\"a b\"]"]
+        );
+    }
+
+    #[test]
+    fn compares_arrays_by_index() {
+        assert_eq!(
+            summary(
+                json!({"items": [{"qty": 1}, 2, 3]}),
+                json!({"items": [{"qty": 5}, 2]}),
+            ),
+            ["~.items[0].qty", "-.items[2]"]
+        );
+        assert_eq!(summary(json!([1]), json!([1, 2])), ["+.[1]"]);
+    }
+
+    #[test]
+    fn replaces_differently_typed_values() {
+        assert_eq!(summary(json!(1), json!("1")), ["~."]);
+        assert_eq!(summary(json!({"a": [1]}), json!({"a": {}})), ["~.a"]);
+    }
+}
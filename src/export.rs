@@ -0,0 +1,156 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Export of detail payloads and instance bundles to files.
+//!
+//! Paths ending in `.ndjson` or `.jsonl` get compact NDJSON, with one line
+//! per array element; other paths get pretty JSON. A bundle is written as a
+//! directory, or as a tarball when the path ends in `.tar`, `.tar.gz` or
+//! `.tgz`.
+
+use anyhow::{Context, Result};
+use flate2::{write::GzEncoder, Compression};
+use runtara_management_sdk::{Checkpoint, InstanceInfo};
+use serde_json::Value;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// An instance with all its checkpoints, oldest first.
+#[derive(Debug)]
+pub struct Bundle {
+    pub info: InstanceInfo,
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+/// Resolve a path typed by the user, expanding a leading `~/`
+pub fn expand_path(text: &str) -> PathBuf {
+    match (text.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(text),
+    }
+}
+
+/// Write `value` to `path`, creating parent directories
+pub fn write_payload(path: &Path, value: &Value) -> Result<()> {
+    let bytes = encode(path, value)?;
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(path, bytes).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Write `bundle` to `path` as a directory or tarball.
+///
+/// The layout is `instance.json` plus `checkpoints/NNNN-<id>.json`, numbered
+/// in creation order; a tarball puts it under a directory named after the
+/// instance.
+pub fn write_bundle(path: &Path, bundle: &Bundle) -> Result<()> {
+    let mut files = vec![(
+        PathBuf::from("instance.json"),
+        encode(
+            Path::new("instance.json"),
+            &serde_json::to_value(&bundle.info)?,
+        )?,
+    )];
+    for (index, checkpoint) in bundle.checkpoints.iter().enumerate() {
+        let name = format!(
+            "{:04}-{}.json",
+            index + 1,
+            file_name(&checkpoint.checkpoint_id)
+        );
+        files.push((
+            Path::new("checkpoints").join(name),
+            encode(
+                Path::new("checkpoint.json"),
+                &serde_json::to_value(checkpoint)?,
+            )?,
+        ));
+    }
+
+    let name = path.to_string_lossy();
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        let file = create_file(path)?;
+        let encoder = write_tar(
+            GzEncoder::new(file, Compression::default()),
+            &bundle.info.instance_id,
+            &files,
+        )?;
+        encoder.finish()?;
+    } else if name.ends_with(".tar") {
+        let file = create_file(path)?;
+        write_tar(file, &bundle.info.instance_id, &files)?.flush()?;
+    } else {
+        for (relative, bytes) in &files {
+            let target = path.join(relative);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            fs::write(&target, bytes)
+                .with_context(|| format!("Failed to write {}", target.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Serialize `value` in the format chosen by the extension of `path`
+fn encode(path: &Path, value: &Value) -> Result<Vec<u8>> {
+    let ndjson = matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("ndjson" | "jsonl")
+    );
+    let mut out = Vec::new();
+    match value {
+        Value::Array(items) if ndjson => {
+            for item in items {
+                serde_json::to_writer(&mut out, item)?;
+                out.push(b'\n');
+            }
+        }
+        _ if ndjson => {
+            serde_json::to_writer(&mut out, value)?;
+            out.push(b'\n');
+        }
+        _ => {
+            serde_json::to_writer_pretty(&mut out, value)?;
+            out.push(b'\n');
+        }
+    }
+    Ok(out)
+}
+
+fn create_file(path: &Path) -> Result<File> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    File::create(path).with_context(|| format!("Failed to create {}", path.display()))
+}
+
+fn write_tar<W: Write>(writer: W, root: &str, files: &[(PathBuf, Vec<u8>)]) -> Result<W> {
+    let mut archive = tar::Builder::new(writer);
+    let root = file_name(root);
+    for (relative, bytes) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(bytes.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+        archive.append_data(
+            &mut header,
+            Path::new(&root).join(relative),
+            bytes.as_slice(),
+        )?;
+    }
+    Ok(archive.into_inner()?)
+}
+
+/// `id` made safe to use as a file name
+fn file_name(id: &str) -> String {
+    id.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '\0' => '_',
+            c => c,
+        })
+        .collect()
+}
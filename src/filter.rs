@@ -0,0 +1,251 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Instance search and filters.
+//!
+//! Filters the server understands are passed in [`ListInstancesOptions`];
+//! the free-text search is applied client-side to the loaded page.
+
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate, NaiveDateTime, Utc};
+use runtara_management_sdk::{InstanceSummary, ListInstancesOptions};
+
+/// Filters for the instances list, in addition to the status filter.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstanceFilter {
+    /// Free text matched against instance ID prefix, image name/ID and tenant
+    pub search: String,
+    /// Image name prefix (server-side)
+    pub image_name_prefix: Option<String>,
+    /// Created at or after (server-side)
+    pub created_after: Option<DateTime<Utc>>,
+    /// Created before (server-side)
+    pub created_before: Option<DateTime<Utc>>,
+    /// Finished at or after (server-side)
+    pub finished_after: Option<DateTime<Utc>>,
+    /// Finished before (server-side)
+    pub finished_before: Option<DateTime<Utc>>,
+}
+
+impl InstanceFilter {
+    /// Parse the structured filter bar, keeping the current search text.
+    ///
+    /// Accepts whitespace-separated terms:
+    /// `created>TIME`, `created<TIME`, `finished>TIME`, `finished<TIME` and
+    /// `image:PREFIX`. See [`parse_time`] for the time formats.
+    pub fn parse_bar(&self, input: &str) -> Result<Self, String> {
+        let mut filter = InstanceFilter {
+            search: self.search.clone(),
+            ..Default::default()
+        };
+
+        for term in input.split_whitespace() {
+            if let Some(prefix) = term.strip_prefix("image:") {
+                if prefix.is_empty() {
+                    return Err("'image:' needs a name prefix".to_string());
+                }
+                filter.image_name_prefix = Some(prefix.to_string());
+                continue;
+            }
+
+            let (field, rest) = term
+                .split_once(['>', '<'])
+                .ok_or_else(|| format!("Unknown filter term '{}'", term))?;
+            let after = term[field.len()..].starts_with('>');
+            // Accept >= and <= as aliases
+            let value = rest.strip_prefix('=').unwrap_or(rest);
+            let time = parse_time(value)?;
+
+            let slot = match (field, after) {
+                ("created", true) => &mut filter.created_after,
+                ("created", false) => &mut filter.created_before,
+                ("finished", true) => &mut filter.finished_after,
+                ("finished", false) => &mut filter.finished_before,
+                _ => return Err(format!("Unknown filter field '{}'", field)),
+            };
+            *slot = Some(time);
+        }
+
+        Ok(filter)
+    }
+
+    /// Render the structured filters back into filter bar syntax
+    pub fn bar_text(&self) -> String {
+        let mut terms = Vec::new();
+        if let Some(ref prefix) = self.image_name_prefix {
+            terms.push(format!("image:{}", prefix));
+        }
+        let times = [
+            ("created>", self.created_after),
+            ("created<", self.created_before),
+            ("finished>", self.finished_after),
+            ("finished<", self.finished_before),
+        ];
+        for (label, time) in times {
+            if let Some(time) = time {
+                terms.push(format!("{}{}", label, time.format("%Y-%m-%dT%H:%M:%SZ")));
+            }
+        }
+        terms.join(" ")
+    }
+
+    /// Whether any structured (server-side) filter is set
+    pub fn has_server_filters(&self) -> bool {
+        self.image_name_prefix.is_some()
+            || self.created_after.is_some()
+            || self.created_before.is_some()
+            || self.finished_after.is_some()
+            || self.finished_before.is_some()
+    }
+
+    /// Copy the server-side filters into list options
+    pub fn apply(&self, options: &mut ListInstancesOptions) {
+        options.image_name_prefix = self.image_name_prefix.clone();
+        options.created_after = self.created_after;
+        options.created_before = self.created_before;
+        options.finished_after = self.finished_after;
+        options.finished_before = self.finished_before;
+    }
+
+    /// Client-side search match; `image_name` is the name of the instance's
+    /// image when known
+    pub fn matches(&self, instance: &InstanceSummary, image_name: Option<&str>) -> bool {
+        let search = self.search.trim().to_lowercase();
+        if search.is_empty() {
+            return true;
+        }
+
+        instance.instance_id.to_lowercase().starts_with(&search)
+            || instance.image_id.to_lowercase().contains(&search)
+            || image_name.is_some_and(|name| name.to_lowercase().contains(&search))
+            || instance.tenant_id.to_lowercase().contains(&search)
+    }
+}
+
+/// Parse a point in time.
+///
+/// Accepts RFC 3339 (`2025-01-31T12:00:00Z`), `2025-01-31 12:00`,
+/// `2025-01-31` (UTC midnight) and relative ages such as `30m`, `6h`, `7d`
+/// meaning that long ago.
+pub fn parse_time(value: &str) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+
+    if let Some(age) = parse_age(value) {
+        return Ok(Utc::now() - age);
+    }
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M"] {
+        if let Ok(time) = NaiveDateTime::parse_from_str(value, format) {
+            return Ok(time.and_utc());
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+
+    Err(format!("Invalid time '{}'", value))
+}
+
+/// Parse a relative age like `30m`, `6h` or `7d`
+pub fn parse_age(value: &str) -> Option<ChronoDuration> {
+    let unit = value.chars().last()?;
+    let amount: i64 = value[..value.len() - unit.len_utf8()].parse().ok()?;
+    match unit {
+        's' => Some(ChronoDuration::seconds(amount)),
+        'm' => Some(ChronoDuration::minutes(amount)),
+        'h' => Some(ChronoDuration::hours(amount)),
+        'd' => Some(ChronoDuration::days(amount)),
+        'w' => Some(ChronoDuration::weeks(amount)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn parses_times() {
+        assert_eq!(
+            parse_time("2025-01-31T12:00:00+02:00"),
+            Ok(at(2025, 1, 31, 10, 0))
+        );
+        assert_eq!(parse_time("2025-01-31 12:30"), Ok(at(2025, 1, 31, 12, 30)));
+        assert_eq!(parse_time("2025-01-31T12:30"), Ok(at(2025, 1, 31, 12, 30)));
+        assert_eq!(parse_time("2025-01-31"), Ok(at(2025, 1, 31, 0, 0)));
+        assert!(parse_time("yesterday").is_err());
+    }
+
+    #[test]
+    fn parses_relative_ages() {
+        assert_eq!(parse_age("30m"), Some(ChronoDuration::minutes(30)));
+        assert_eq!(parse_age("2w"), Some(ChronoDuration::weeks(2)));
+        assert_eq!(parse_age("6x"), None);
+        assert_eq!(parse_age("h"), None);
+        assert_eq!(parse_age(""), None);
+
+        let time = parse_time("6h").unwrap();
+        let age = Utc::now() - time;
+        assert!(
+            age >= ChronoDuration::hours(6)
+                && age < ChronoDuration::hours(6) + ChronoDuration::minutes(1)
+        );
+    }
+
+    #[test]
+    fn parses_the_filter_bar() {
+        let current = InstanceFilter {
+            search: "abc".to_string(),
+            image_name_prefix: Some("old".to_string()),
+            ..Default::default()
+        };
+        let filter = current
+            .parse_bar("image:orders created>=2025-01-01 finished<2025-02-01T08:00:00Z")
+            .unwrap();
+        assert_eq!(
+            filter,
+            InstanceFilter {
+                search: "abc".to_string(),
+                image_name_prefix: Some("orders".to_string()),
+                created_after: Some(at(2025, 1, 1, 0, 0)),
+                finished_before: Some(at(2025, 2, 1, 8, 0)),
+                ..Default::default()
+            }
+        );
+        assert!(filter.has_server_filters());
+
+        let cleared = filter.parse_bar("  ").unwrap();
+        assert!(!cleared.has_server_filters());
+        assert_eq!(cleared.search, "abc");
+    }
+
+    #[test]
+    fn rejects_bad_filter_terms() {
+        let filter = InstanceFilter::default();
+        assert!(filter.parse_bar("image:").is_err());
+        assert!(filter.parse_bar("orders").is_err());
+        assert!(filter.parse_bar("started>1h").is_err());
+        assert!(filter.parse_bar("created>soon").is_err());
+    }
+
+    #[test]
+    fn bar_text_round_trips() {
+        let filter = InstanceFilter {
+            image_name_prefix: Some("orders".to_string()),
+            created_before: Some(at(2025, 1, 31, 12, 0)),
+            finished_after: Some(at(2025, 1, 1, 0, 0)),
+            ..Default::default()
+        };
+        let text = filter.bar_text();
+        assert_eq!(
+            text,
+            "image:orders created<2025-01-31T12:00:00Z finished>2025-01-01T00:00:00Z"
+        );
+        assert_eq!(InstanceFilter::default().parse_bar(&text), Ok(filter));
+    }
+}
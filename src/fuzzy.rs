@@ -0,0 +1,47 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Fuzzy matching for pickers.
+//!
+//! A pattern matches when its characters appear in order in the candidate,
+//! ignoring case. Runs of consecutive characters and matches at the start of
+//! a word rank higher, so `acp` ranks `acme-prod` above `alpha-cache-prod`.
+
+/// Score of `candidate` for `pattern`, or `None` when it does not match
+pub fn score(pattern: &str, candidate: &str) -> Option<i32> {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    if pattern == candidate {
+        return Some(i32::MAX);
+    }
+
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+    for c in pattern {
+        let index = next + candidate[next..].iter().position(|&d| d == c)?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == index) {
+            score += 5;
+        }
+        if index == 0 || !candidate[index - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(index);
+        next = index + 1;
+    }
+    // Shorter candidates leave less unmatched
+    Some(score * 100 - candidate.len() as i32)
+}
+
+/// Candidates matching `pattern`, best first; ties keep their order
+pub fn rank<'a>(pattern: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    let mut matches: Vec<(i32, &str)> = candidates
+        .into_iter()
+        .filter_map(|candidate| score(pattern, candidate).map(|score| (score, candidate)))
+        .collect();
+    matches.sort_by(|(a, _), (b, _)| b.cmp(a));
+    matches
+        .into_iter()
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
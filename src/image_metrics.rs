@@ -0,0 +1,135 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Metrics of one image.
+//!
+//! The server reports metrics per tenant only, so the instances created from
+//! an image within the metrics range are listed and summarised on the
+//! client: runs by outcome, failure rate and run time percentiles, in total
+//! and per time bucket.
+
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use runtara_management_sdk::{InstanceStatus, InstanceSummary, MetricsGranularity};
+
+use crate::worker::ImageInstances;
+
+/// Instances counted at most; beyond this the newest are used
+pub const IMAGE_METRICS_LIMIT: usize = 1000;
+
+/// Outcomes and run times of a set of instances.
+#[derive(Debug, Default)]
+pub struct ImageStats {
+    pub invocations: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub cancelled: usize,
+    /// Pending, running or suspended
+    pub unfinished: usize,
+    /// Run times of finished instances that started, shortest first
+    durations: Vec<Duration>,
+}
+
+impl ImageStats {
+    fn add(&mut self, instance: &InstanceSummary) {
+        self.invocations += 1;
+        match instance.status {
+            InstanceStatus::Completed => self.completed += 1,
+            InstanceStatus::Failed => self.failed += 1,
+            InstanceStatus::Cancelled => self.cancelled += 1,
+            _ => self.unfinished += 1,
+        }
+        if let (Some(started), Some(finished)) = (instance.started_at, instance.finished_at) {
+            self.durations.push(finished - started);
+        }
+    }
+
+    fn finish(&mut self) {
+        self.durations.sort();
+    }
+
+    /// Failed share of finished instances, in percent
+    pub fn failure_rate(&self) -> Option<f64> {
+        let finished = self.completed + self.failed + self.cancelled;
+        (finished > 0).then(|| self.failed as f64 * 100.0 / finished as f64)
+    }
+
+    /// Run time below which `percent` of the runs finished (nearest rank)
+    pub fn percentile(&self, percent: f64) -> Option<Duration> {
+        let rank = (percent / 100.0 * self.durations.len() as f64).ceil() as usize;
+        self.durations.get(rank.saturating_sub(1)).copied()
+    }
+
+    pub fn max_duration(&self) -> Option<Duration> {
+        self.durations.last().copied()
+    }
+}
+
+/// Metrics of one image over a time range.
+#[derive(Debug)]
+pub struct ImageMetrics {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub granularity: MetricsGranularity,
+    /// Instances created in the range; only the newest
+    /// [`IMAGE_METRICS_LIMIT`] are counted
+    pub matched: u32,
+    pub total: ImageStats,
+    /// Stats per bucket, newest first
+    pub buckets: Vec<(DateTime<Utc>, ImageStats)>,
+    instances: Vec<InstanceSummary>,
+}
+
+impl ImageMetrics {
+    /// Summarise the listed instances of an image
+    pub fn new(listed: ImageInstances, granularity: MetricsGranularity) -> Self {
+        let mut metrics = Self {
+            start: listed.start,
+            end: listed.end,
+            granularity,
+            matched: listed.total,
+            total: ImageStats::default(),
+            buckets: Vec::new(),
+            instances: listed.instances,
+        };
+        metrics.group(granularity);
+        metrics
+    }
+
+    /// Instances counted
+    pub fn counted(&self) -> usize {
+        self.instances.len()
+    }
+
+    /// Group the instances into buckets of `granularity`
+    pub fn group(&mut self, granularity: MetricsGranularity) {
+        let width = match granularity {
+            MetricsGranularity::Hourly => Duration::hours(1),
+            MetricsGranularity::Daily => Duration::days(1),
+        };
+        let mut total = ImageStats::default();
+        let mut buckets: Vec<(DateTime<Utc>, ImageStats)> = Vec::new();
+        for instance in &self.instances {
+            total.add(instance);
+            let bucket = instance
+                .created_at
+                .duration_trunc(width)
+                .unwrap_or(instance.created_at);
+            match buckets.iter_mut().find(|(time, _)| *time == bucket) {
+                Some((_, stats)) => stats.add(instance),
+                None => {
+                    let mut stats = ImageStats::default();
+                    stats.add(instance);
+                    buckets.push((bucket, stats));
+                }
+            }
+        }
+        total.finish();
+        for (_, stats) in &mut buckets {
+            stats.finish();
+        }
+        buckets.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        self.granularity = granularity;
+        self.total = total;
+        self.buckets = buckets;
+    }
+}
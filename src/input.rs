@@ -0,0 +1,86 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Single-line text input.
+
+/// Editable line of text with a cursor.
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    value: String,
+    /// Cursor position in characters
+    cursor: usize,
+}
+
+impl TextInput {
+    /// Create an input pre-filled with `value`, cursor at the end
+    pub fn new(value: impl Into<String>) -> Self {
+        let value = value.into();
+        let cursor = value.chars().count();
+        Self { value, cursor }
+    }
+
+    /// Replace the text, cursor at the end
+    pub fn set(&mut self, value: impl Into<String>) {
+        *self = Self::new(value);
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Cursor position in characters
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn insert(&mut self, c: char) {
+        let index = self.byte_index();
+        self.value.insert(index, c);
+        self.cursor += 1;
+    }
+
+    /// Delete the character before the cursor
+    pub fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            let index = self.byte_index();
+            self.value.remove(index);
+        }
+    }
+
+    /// Delete the character under the cursor
+    pub fn delete(&mut self) {
+        if self.cursor < self.value.chars().count() {
+            let index = self.byte_index();
+            self.value.remove(index);
+        }
+    }
+
+    pub fn left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.value.chars().count());
+    }
+
+    pub fn home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn end(&mut self) {
+        self.cursor = self.value.chars().count();
+    }
+
+    pub fn clear(&mut self) {
+        self.value.clear();
+        self.cursor = 0;
+    }
+
+    fn byte_index(&self) -> usize {
+        self.value
+            .char_indices()
+            .nth(self.cursor)
+            .map(|(i, _)| i)
+            .unwrap_or(self.value.len())
+    }
+}
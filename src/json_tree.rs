@@ -0,0 +1,527 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Collapsible tree view of a JSON value.
+//!
+//! Only expanded containers contribute rows, so large checkpoint data stays
+//! cheap to navigate. Nodes are identified by their JSON pointer (RFC 6901),
+//! which keeps the expansion state when the value is reloaded. A [`Query`]
+//! narrows the tree to the matching subtrees.
+
+use serde_json::Value;
+use std::collections::HashSet;
+
+use crate::app::Viewport;
+use crate::query::{Match, Query};
+
+/// Longest string shown on a row, in characters
+const STRING_PREVIEW: usize = 200;
+
+/// Rows that expanding top-level containers may add when a tree is opened
+const AUTO_EXPAND_ROWS: usize = 200;
+
+/// One visible line of the tree.
+#[derive(Debug, Clone)]
+pub struct TreeRow {
+    /// JSON pointer of the node
+    pub pointer: String,
+    /// Nesting level; children of the root (or query matches) are at depth 0
+    pub depth: usize,
+    /// Object key, array index or match path; `None` for a scalar root
+    pub key: Option<Key>,
+    pub kind: RowKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum Key {
+    Field(String),
+    Index(usize),
+    /// Location of a query match
+    Path(String),
+}
+
+/// What a row shows.
+#[derive(Debug, Clone)]
+pub enum RowKind {
+    Object {
+        len: usize,
+        expanded: bool,
+    },
+    Array {
+        len: usize,
+        expanded: bool,
+    },
+    /// JSON-quoted string, shortened to [`STRING_PREVIEW`] characters
+    String(String),
+    Number(String),
+    Bool(bool),
+    Null,
+}
+
+/// Tree of a JSON value with a cursor.
+#[derive(Debug, Default)]
+pub struct JsonTree {
+    value: Value,
+    /// Pointers of expanded containers
+    expanded: HashSet<String>,
+    /// Active query and the nodes it selected
+    query: Option<Query>,
+    matches: Vec<Match>,
+    rows: Vec<TreeRow>,
+    /// Index of the selected row
+    selected: usize,
+    /// Scroll position, kept by the UI so the cursor stays in view
+    view: Viewport,
+}
+
+impl JsonTree {
+    /// Tree with the smaller top-level containers expanded, as long as
+    /// they add at most [`AUTO_EXPAND_ROWS`] rows in total
+    pub fn new(value: Value) -> Self {
+        let expanded = top_level_containers(&value);
+        let mut tree = Self {
+            value,
+            expanded,
+            ..Default::default()
+        };
+        tree.rebuild();
+        tree
+    }
+
+    /// Replace the value, keeping expanded nodes and the selected node where
+    /// they still exist
+    pub fn set_value(&mut self, value: Value) {
+        let selected = self.selected_pointer().map(str::to_string);
+        self.value = value;
+        if let Some(query) = &self.query {
+            self.matches = query.run(&self.value);
+        }
+        self.rebuild();
+        if let Some(pointer) = selected {
+            self.select_pointer(&pointer);
+        }
+    }
+
+    /// Show only the nodes selected by `query`, or everything with `None`
+    pub fn set_query(&mut self, query: Option<Query>) {
+        self.matches = match &query {
+            Some(query) => query.run(&self.value),
+            None => Vec::new(),
+        };
+        // A single match is most likely the node the user was after
+        if let [single] = self.matches.as_slice() {
+            self.expanded.insert(single.pointer.clone());
+        }
+        self.query = query;
+        self.selected = 0;
+        self.view.reset();
+        self.rebuild();
+    }
+
+    pub fn query(&self) -> Option<&Query> {
+        self.query.as_ref()
+    }
+
+    pub fn rows(&self) -> &[TreeRow] {
+        &self.rows
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// JSON pointer of the selected node
+    pub fn selected_pointer(&self) -> Option<&str> {
+        self.rows.get(self.selected).map(|row| row.pointer.as_str())
+    }
+
+    /// Value of the selected node
+    pub fn selected_value(&self) -> Option<&Value> {
+        self.value.pointer(self.selected_pointer()?)
+    }
+
+    /// Size of the root, e.g. `{3 keys}` or `[12 items]`, or the number of
+    /// query matches
+    pub fn summary(&self) -> String {
+        if self.query.is_some() {
+            return match self.matches.len() {
+                1 => "1 match".to_string(),
+                n => format!("{} matches", n),
+            };
+        }
+        match &self.value {
+            Value::Object(map) => container_summary(false, map.len()),
+            Value::Array(items) => container_summary(true, items.len()),
+            _ => String::new(),
+        }
+    }
+
+    /// Rows to draw in a view `height` rows high, scrolled so the cursor is
+    /// visible
+    pub fn visible_rows(&self, height: usize) -> (usize, &[TreeRow]) {
+        let offset = self.view.scroll(self.selected, self.rows.len(), height);
+        let end = (offset + height).min(self.rows.len());
+        (offset, &self.rows[offset..end])
+    }
+
+    pub fn next(&mut self) {
+        self.select(self.selected + 1);
+    }
+
+    pub fn previous(&mut self) {
+        self.select(self.selected.saturating_sub(1));
+    }
+
+    pub fn page_down(&mut self) {
+        self.select(self.selected + self.view.page());
+    }
+
+    pub fn page_up(&mut self) {
+        self.select(self.selected.saturating_sub(self.view.page()));
+    }
+
+    pub fn first(&mut self) {
+        self.select(0);
+    }
+
+    pub fn last(&mut self) {
+        self.select(self.rows.len().saturating_sub(1));
+    }
+
+    /// Expand the selected container, or step into it when already expanded
+    pub fn expand(&mut self) {
+        let Some(row) = self.rows.get(self.selected) else {
+            return;
+        };
+        match row.kind {
+            RowKind::Object {
+                expanded: false, ..
+            }
+            | RowKind::Array {
+                expanded: false, ..
+            } => {
+                self.expanded.insert(row.pointer.clone());
+                self.rebuild();
+            }
+            RowKind::Object { len, .. } | RowKind::Array { len, .. } if len > 0 => self.next(),
+            _ => {}
+        }
+    }
+
+    /// Collapse the selected container, or go to the parent
+    pub fn collapse(&mut self) {
+        let Some(row) = self.rows.get(self.selected) else {
+            return;
+        };
+        match row.kind {
+            RowKind::Object { expanded: true, .. } | RowKind::Array { expanded: true, .. } => {
+                self.expanded.remove(&row.pointer);
+                self.rebuild();
+            }
+            _ => self.parent(),
+        }
+    }
+
+    /// Expand or collapse the selected container
+    pub fn toggle(&mut self) {
+        let Some(row) = self.rows.get(self.selected) else {
+            return;
+        };
+        if matches!(row.kind, RowKind::Object { .. } | RowKind::Array { .. }) {
+            let pointer = row.pointer.clone();
+            if !self.expanded.remove(&pointer) {
+                self.expanded.insert(pointer);
+            }
+            self.rebuild();
+        }
+    }
+
+    /// Select the parent of the selected node
+    pub fn parent(&mut self) {
+        let Some(depth) = self.rows.get(self.selected).map(|row| row.depth) else {
+            return;
+        };
+        if let Some(index) = self.rows[..self.selected]
+            .iter()
+            .rposition(|row| row.depth < depth)
+        {
+            self.selected = index;
+        }
+    }
+
+    /// Select the next node with the same parent
+    pub fn next_sibling(&mut self) {
+        let Some(depth) = self.rows.get(self.selected).map(|row| row.depth) else {
+            return;
+        };
+        if let Some(index) = self.rows[self.selected + 1..]
+            .iter()
+            .position(|row| row.depth <= depth)
+            .map(|i| self.selected + 1 + i)
+            .filter(|&i| self.rows[i].depth == depth)
+        {
+            self.selected = index;
+        }
+    }
+
+    /// Select the previous node with the same parent
+    pub fn previous_sibling(&mut self) {
+        let Some(depth) = self.rows.get(self.selected).map(|row| row.depth) else {
+            return;
+        };
+        if let Some(index) = self.rows[..self.selected]
+            .iter()
+            .rposition(|row| row.depth <= depth)
+            .filter(|&i| self.rows[i].depth == depth)
+        {
+            self.selected = index;
+        }
+    }
+
+    /// Expand every container below the selected node, or everything when
+    /// nothing is selected
+    pub fn expand_all(&mut self) {
+        let pointer = self.selected_pointer().unwrap_or("").to_string();
+        if let Some(node) = self.value.pointer(&pointer) {
+            collect_containers(node, &pointer, &mut self.expanded);
+        }
+        self.rebuild();
+    }
+
+    /// Collapse everything back to the top level
+    pub fn collapse_all(&mut self) {
+        let selected = self.selected_pointer().map(str::to_string);
+        self.expanded.clear();
+        self.rebuild();
+        // Select the top-level ancestor of the previous selection
+        if let Some(pointer) = selected {
+            self.select_pointer(&pointer);
+        }
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.rows.len().saturating_sub(1));
+    }
+
+    /// Select the row of `pointer`, or its closest visible ancestor
+    fn select_pointer(&mut self, pointer: &str) {
+        let mut pointer = pointer;
+        loop {
+            if let Some(index) = self.rows.iter().position(|row| row.pointer == pointer) {
+                self.selected = index;
+                return;
+            }
+            match pointer.rfind('/') {
+                Some(end) if !pointer.is_empty() => pointer = &pointer[..end],
+                _ => {
+                    self.select(self.selected);
+                    return;
+                }
+            }
+        }
+    }
+
+    fn rebuild(&mut self) {
+        self.rows.clear();
+        if self.query.is_some() {
+            for m in &self.matches {
+                if let Some(node) = self.value.pointer(&m.pointer) {
+                    push_rows(
+                        &mut self.rows,
+                        &self.expanded,
+                        node,
+                        m.pointer.clone(),
+                        0,
+                        Key::Path(m.label.clone()),
+                    );
+                }
+            }
+            self.select(self.selected);
+            return;
+        }
+
+        match &self.value {
+            Value::Object(map) => {
+                for (key, child) in map {
+                    let pointer = format!("/{}", escape(key));
+                    push_rows(
+                        &mut self.rows,
+                        &self.expanded,
+                        child,
+                        pointer,
+                        0,
+                        Key::Field(key.clone()),
+                    );
+                }
+            }
+            Value::Array(items) => {
+                for (index, child) in items.iter().enumerate() {
+                    push_rows(
+                        &mut self.rows,
+                        &self.expanded,
+                        child,
+                        format!("/{}", index),
+                        0,
+                        Key::Index(index),
+                    );
+                }
+            }
+            scalar => self.rows.push(TreeRow {
+                pointer: String::new(),
+                depth: 0,
+                key: None,
+                kind: scalar_kind(scalar),
+            }),
+        }
+        self.select(self.selected);
+    }
+}
+
+/// Append the row of `value` and, when expanded, its descendants
+fn push_rows(
+    rows: &mut Vec<TreeRow>,
+    expanded: &HashSet<String>,
+    value: &Value,
+    pointer: String,
+    depth: usize,
+    key: Key,
+) {
+    let is_expanded = expanded.contains(&pointer);
+    let kind = match value {
+        Value::Object(map) => RowKind::Object {
+            len: map.len(),
+            expanded: is_expanded,
+        },
+        Value::Array(items) => RowKind::Array {
+            len: items.len(),
+            expanded: is_expanded,
+        },
+        scalar => scalar_kind(scalar),
+    };
+    rows.push(TreeRow {
+        pointer: pointer.clone(),
+        depth,
+        key: Some(key),
+        kind,
+    });
+
+    if !is_expanded {
+        return;
+    }
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let child_pointer = format!("{}/{}", pointer, escape(key));
+                push_rows(
+                    rows,
+                    expanded,
+                    child,
+                    child_pointer,
+                    depth + 1,
+                    Key::Field(key.clone()),
+                );
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter().enumerate() {
+                let child_pointer = format!("{}/{}", pointer, index);
+                push_rows(
+                    rows,
+                    expanded,
+                    child,
+                    child_pointer,
+                    depth + 1,
+                    Key::Index(index),
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+fn scalar_kind(value: &Value) -> RowKind {
+    match value {
+        Value::String(s) => RowKind::String(quote_preview(s)),
+        Value::Number(n) => RowKind::Number(n.to_string()),
+        Value::Bool(b) => RowKind::Bool(*b),
+        _ => RowKind::Null,
+    }
+}
+
+/// JSON-quote `s`, cut to [`STRING_PREVIEW`] characters
+fn quote_preview(s: &str) -> String {
+    match s.char_indices().nth(STRING_PREVIEW) {
+        Some((end, _)) => {
+            let quoted = Value::String(s[..end].to_string()).to_string();
+            format!(
+                "{}…\" ({} chars)",
+                &quoted[..quoted.len() - 1],
+                s.chars().count()
+            )
+        }
+        None => Value::String(s.to_string()).to_string(),
+    }
+}
+
+/// `{N keys}` or `[N items]`
+pub fn container_summary(is_array: bool, len: usize) -> String {
+    match (is_array, len) {
+        (false, 1) => "{1 key}".to_string(),
+        (false, n) => format!("{{{} keys}}", n),
+        (true, 1) => "[1 item]".to_string(),
+        (true, n) => format!("[{} items]", n),
+    }
+}
+
+/// Pointers of the containers directly below the root, in order, while
+/// their children fit in [`AUTO_EXPAND_ROWS`]
+fn top_level_containers(value: &Value) -> HashSet<String> {
+    let children: Box<dyn Iterator<Item = (String, &Value)>> = match value {
+        Value::Object(map) => Box::new(map.iter().map(|(k, v)| (format!("/{}", escape(k)), v))),
+        Value::Array(items) => Box::new(
+            items
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (format!("/{}", i), v)),
+        ),
+        _ => return HashSet::new(),
+    };
+    let mut budget = AUTO_EXPAND_ROWS;
+    let mut expanded = HashSet::new();
+    for (pointer, child) in children {
+        let len = match child {
+            Value::Object(map) => map.len(),
+            Value::Array(items) => items.len(),
+            _ => continue,
+        };
+        if len > budget {
+            break;
+        }
+        budget -= len;
+        expanded.insert(pointer);
+    }
+    expanded
+}
+
+/// Add the pointers of `value` and every container below it
+fn collect_containers(value: &Value, pointer: &str, expanded: &mut HashSet<String>) {
+    match value {
+        Value::Object(map) => {
+            expanded.insert(pointer.to_string());
+            for (key, child) in map {
+                collect_containers(child, &format!("{}/{}", pointer, escape(key)), expanded);
+            }
+        }
+        Value::Array(items) => {
+            expanded.insert(pointer.to_string());
+            for (index, child) in items.iter().enumerate() {
+                collect_containers(child, &format!("{}/{}", pointer, index), expanded);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Escape a key for use in a JSON pointer
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
@@ -3,50 +3,151 @@
 //! Runtara TUI - Terminal UI for monitoring Runtara instances and images.
 
 mod app;
+mod cli;
+mod clipboard;
+mod compare;
+mod config;
+mod connection;
+mod diff;
+mod export;
+mod filter;
+mod fuzzy;
+mod image_metrics;
+mod input;
+mod json_tree;
+mod notify;
+mod query;
+mod range;
+mod server;
+mod sort;
+mod timeline;
 mod ui;
+mod worker;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::Parser;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{
+        DisableMouseCapture, EnableMouseCapture, Event, EventStream, KeyCode, KeyEvent,
+        KeyEventKind, KeyModifiers,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use futures::StreamExt;
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
+use std::path::PathBuf;
 use std::time::Duration;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 
-use app::{App, ViewMode};
+use app::{App, InstanceAction, Jump, PromptKind, ViewMode};
+use config::{Config, Profile};
+use json_tree::JsonTree;
+use worker::ServerUpdate;
+
+/// App connected using a profile, with its update channel, or why it could
+/// not be set up
+type Switched = (String, Result<(App, UnboundedReceiver<ServerUpdate>)>);
 
 #[derive(Parser, Debug)]
 #[command(name = "runtara-tui")]
 #[command(about = "Terminal UI for monitoring Runtara instances and images")]
 struct Args {
-    /// Runtara environment server address
+    /// Run a single command and print its result instead of starting the UI
+    #[command(subcommand)]
+    command: Option<cli::Command>,
+
+    /// Profiles from the config file; several (comma-separated or repeated)
+    /// are monitored together (default: `default_profile`)
+    #[arg(
+        short = 'P',
+        long,
+        env = "RUNTARA_PROFILE",
+        value_delimiter = ',',
+        global = true
+    )]
+    profile: Vec<String>,
+
+    /// Config file (default: $XDG_CONFIG_HOME/runtara-tui/config.toml)
+    #[arg(long, env = "RUNTARA_TUI_CONFIG", global = true)]
+    config: Option<PathBuf>,
+
+    /// Runtara environment server address as HOST:PORT; several
+    /// (comma-separated or repeated) are monitored together
+    /// (default: 127.0.0.1:8002)
     #[arg(
         short,
         long,
         env = "RUNTARA_ENV_ADDR",
-        default_value = "127.0.0.1:8002"
+        value_delimiter = ',',
+        global = true
     )]
-    server: String,
+    server: Vec<String>,
 
-    /// Skip TLS certificate verification (default: true for local dev)
-    #[arg(long, env = "RUNTARA_SKIP_CERT_VERIFICATION", default_value = "true")]
-    skip_cert_verification: bool,
+    /// Expected name in the server certificate (default: the server host)
+    #[arg(long, env = "RUNTARA_SERVER_NAME", global = true)]
+    server_name: Option<String>,
 
-    /// Refresh interval in seconds
-    #[arg(short, long, default_value = "5")]
-    refresh: u64,
+    /// Skip TLS certificate verification (development only)
+    #[arg(
+        long,
+        env = "RUNTARA_SKIP_CERT_VERIFICATION",
+        num_args = 0..=1,
+        default_missing_value = "true",
+        global = true
+    )]
+    skip_cert_verification: Option<bool>,
 
-    /// Tenant ID filter (optional)
+    /// Refresh interval in seconds (default: 5)
     #[arg(short, long)]
+    refresh: Option<u64>,
+
+    /// Heartbeat age in seconds after which a running instance is shown as
+    /// stale (default: 120)
+    #[arg(long)]
+    stale_after: Option<u64>,
+
+    /// Tenant ID filter (optional)
+    #[arg(short, long, global = true)]
     tenant: Option<String>,
 }
 
+impl Args {
+    /// Command line settings, which take precedence over the profile.
+    ///
+    /// The server is only included when exactly one is given.
+    fn overrides(&self) -> Profile {
+        Profile {
+            server: match self.server.as_slice() {
+                [server] => Some(server.clone()),
+                _ => None,
+            },
+            server_name: self.server_name.clone(),
+            skip_cert_verification: self.skip_cert_verification,
+            tenant: self.tenant.clone(),
+            refresh: self.refresh,
+            stale_after: self.stale_after,
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
+    let config = Config::load(args.config.as_deref())?;
+    let settings = config.server_settings(&args.profile, &args.server, &args.overrides())?;
+
+    if let Some(command) = args.command {
+        let [settings] = settings.as_slice() else {
+            bail!("Commands take a single server or profile");
+        };
+        return cli::run(command, settings).await;
+    }
+
+    // Create app
+    let (updates_tx, updates_rx) = mpsc::unbounded_channel();
+    let mut app = App::new(&settings, config.profile_names(), updates_tx).await?;
 
     // Setup terminal
     enable_raw_mode()?;
@@ -55,15 +156,7 @@ async fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create app and run
-    let mut app = App::new(
-        &args.server,
-        args.skip_cert_verification,
-        args.tenant,
-        Duration::from_secs(args.refresh),
-    );
-
-    let res = run_app(&mut terminal, &mut app).await;
+    let res = run_app(&mut terminal, &mut app, updates_rx, &config).await;
 
     // Restore terminal
     disable_raw_mode()?;
@@ -84,73 +177,388 @@ async fn main() -> Result<()> {
 async fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
+    mut updates: UnboundedReceiver<ServerUpdate>,
+    config: &Config,
 ) -> Result<()> {
+    let mut events = EventStream::new();
+    // Redraw periodically so countdowns and ages stay current
+    let mut tick = tokio::time::interval(Duration::from_millis(250));
+    let (switched_tx, mut switched_rx) = mpsc::unbounded_channel::<Switched>();
+
     // Initial data fetch
-    app.refresh().await;
+    app.refresh();
 
     loop {
         terminal.draw(|f| ui::draw(f, app))?;
 
-        // Poll for events with timeout for auto-refresh
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    // Handle keys based on current view mode
-                    match app.view_mode {
-                        ViewMode::List => match key.code {
-                            KeyCode::Char('q') => return Ok(()),
-                            KeyCode::Esc => return Ok(()),
-                            KeyCode::Char('r') => app.refresh().await,
-                            KeyCode::Tab => app.next_tab(),
-                            KeyCode::BackTab => app.previous_tab(),
-                            KeyCode::Down | KeyCode::Char('j') => app.next_item(),
-                            KeyCode::Up | KeyCode::Char('k') => app.previous_item(),
-                            KeyCode::Char('1') => app.set_tab(0),
-                            KeyCode::Char('2') => app.set_tab(1),
-                            KeyCode::Char('3') => app.set_tab(2),
-                            KeyCode::Char('4') => app.set_tab(3),
-                            KeyCode::Char('f') => app.cycle_status_filter(),
-                            KeyCode::Char('g') => {
-                                if app.tab == app::Tab::Metrics {
-                                    app.toggle_metrics_granularity();
-                                    app.refresh().await;
-                                }
-                            }
-                            KeyCode::Enter => {
-                                if app.tab == app::Tab::Instances {
-                                    app.open_instance_detail().await;
-                                }
-                            }
-                            _ => {}
-                        },
-                        ViewMode::InstanceDetail => match key.code {
-                            KeyCode::Esc => app.go_back(),
-                            KeyCode::Char('c') => app.open_checkpoints_list().await,
-                            KeyCode::Down | KeyCode::Char('j') => app.scroll_down(),
-                            KeyCode::Up | KeyCode::Char('k') => app.scroll_up(),
-                            _ => {}
-                        },
-                        ViewMode::CheckpointsList => match key.code {
-                            KeyCode::Esc => app.go_back(),
-                            KeyCode::Enter => app.open_checkpoint_detail().await,
-                            KeyCode::Down | KeyCode::Char('j') => app.next_checkpoint(),
-                            KeyCode::Up | KeyCode::Char('k') => app.previous_checkpoint(),
-                            _ => {}
-                        },
-                        ViewMode::CheckpointDetail => match key.code {
-                            KeyCode::Esc => app.go_back(),
-                            KeyCode::Down | KeyCode::Char('j') => app.scroll_down(),
-                            KeyCode::Up | KeyCode::Char('k') => app.scroll_up(),
-                            _ => {}
-                        },
+        tokio::select! {
+            event = events.next() => match event {
+                Some(Ok(Event::Key(key))) => {
+                    if key.kind == KeyEventKind::Press && handle_key(app, key) {
+                        return Ok(());
                     }
                 }
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e.into()),
+                None => return Ok(()),
+            },
+            Some(update) = updates.recv() => app.apply(update),
+            Some((name, result)) = switched_rx.recv() => {
+                if let Some(receiver) = finish_switch(app, &name, result) {
+                    updates = receiver;
+                }
             }
+            _ = tick.tick() => {}
+        }
+
+        if let Some(name) = app.requested_profile.take() {
+            start_switch(app, config, name, switched_tx.clone());
         }
 
         // Auto-refresh check
         if app.should_refresh() {
-            app.refresh().await;
+            app.refresh();
+        }
+        app.poll_follow();
+    }
+}
+
+/// Start setting up an app connected using profile `name`.
+///
+/// Resolving the server address may take a while, so the new app is built
+/// on a spawned task and sent to `switched`; the current app stays usable
+/// meanwhile. Command line overrides only apply to the initial profile.
+fn start_switch(app: &mut App, config: &Config, name: String, switched: UnboundedSender<Switched>) {
+    let settings = match config.settings(Some(&name), &Profile::default()) {
+        Ok(settings) => settings,
+        Err(e) => {
+            app.error = Some(format!("Failed to switch to profile '{}': {:#}", name, e));
+            return;
+        }
+    };
+    let profiles = config.profile_names();
+    app.switching_profile = Some(name.clone());
+    tokio::spawn(async move {
+        let (updates_tx, updates_rx) = mpsc::unbounded_channel();
+        let result = App::new(&[settings], profiles, updates_tx)
+            .await
+            .map(|app| (app, updates_rx));
+        let _ = switched.send((name, result));
+    });
+}
+
+/// Replace `app` with the one set up for profile `name`.
+///
+/// Returns the update channel of the new app. A switch superseded by a later
+/// one is dropped. On failure the current app is kept and the error is shown.
+fn finish_switch(
+    app: &mut App,
+    name: &str,
+    result: Result<(App, UnboundedReceiver<ServerUpdate>)>,
+) -> Option<UnboundedReceiver<ServerUpdate>> {
+    if app.switching_profile.as_deref() != Some(name) {
+        return None;
+    }
+    app.switching_profile = None;
+
+    match result {
+        Ok((mut new_app, updates_rx)) => {
+            new_app.inherit_view(app);
+            // Dropping the old app closes its connection; its late results
+            // go to the old channel and are discarded
+            *app = new_app;
+            app.refresh();
+            Some(updates_rx)
+        }
+        Err(e) => {
+            app.error = Some(format!("Failed to switch to profile '{}': {:#}", name, e));
+            None
+        }
+    }
+}
+
+/// Handle a key press. Returns `true` when the app should quit.
+fn handle_key(app: &mut App, key: KeyEvent) -> bool {
+    // Any key dismisses the error popup
+    if app.error.is_some() {
+        app.dismiss_error();
+        return false;
+    }
+
+    // The confirmation dialog captures all keys
+    if app.pending_action.is_some() {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => app.confirm_action(),
+            KeyCode::Char('n') | KeyCode::Esc => app.cancel_action(),
+            _ => {}
         }
+        return false;
+    }
+
+    // The profile switcher captures all keys
+    if app.profile_picker.is_some() {
+        match key.code {
+            KeyCode::Enter => app.select_profile(),
+            KeyCode::Esc | KeyCode::Char('q') => app.close_profile_picker(),
+            KeyCode::Down | KeyCode::Char('j') => app.next_profile(),
+            KeyCode::Up | KeyCode::Char('k') => app.previous_profile(),
+            _ => {}
+        }
+        return false;
+    }
+
+    // The metrics range picker captures all keys
+    if app.range_picker.is_some() {
+        match key.code {
+            KeyCode::Enter => app.select_range(),
+            KeyCode::Esc | KeyCode::Char('q') => app.close_range_picker(),
+            KeyCode::Down | KeyCode::Char('j') => app.next_range(),
+            KeyCode::Up | KeyCode::Char('k') => app.previous_range(),
+            _ => {}
+        }
+        return false;
+    }
+
+    // The tenant picker captures all keys
+    if app.tenant_picker.is_some() {
+        match key.code {
+            KeyCode::Enter => app.select_tenant(),
+            KeyCode::Char(' ') => app.toggle_tenant_mark(),
+            KeyCode::Char('c') => app.compare_marked_tenants(),
+            KeyCode::Esc | KeyCode::Char('q') => app.close_tenant_picker(),
+            KeyCode::Down | KeyCode::Char('j') => app.next_tenant(),
+            KeyCode::Up | KeyCode::Char('k') => app.previous_tenant(),
+            _ => {}
+        }
+        return false;
+    }
+
+    // An open prompt captures all keys
+    if let Some(prompt) = app.prompt.as_mut() {
+        let choosing = prompt.kind == PromptKind::Tenant;
+        let exporting = prompt.kind == PromptKind::Export;
+        let input = &mut prompt.input;
+        match key.code {
+            KeyCode::Enter => app.submit_prompt(),
+            KeyCode::Esc => app.cancel_prompt(),
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                input.clear();
+                app.prompt_changed();
+            }
+            KeyCode::Char(c) => {
+                input.insert(c);
+                app.prompt_changed();
+            }
+            KeyCode::Backspace => {
+                input.backspace();
+                app.prompt_changed();
+            }
+            KeyCode::Delete => {
+                input.delete();
+                app.prompt_changed();
+            }
+            KeyCode::Tab if exporting => app.next_export_part(),
+            KeyCode::Up if choosing => app.previous_tenant_choice(),
+            KeyCode::Down if choosing => app.next_tenant_choice(),
+            KeyCode::Up => app.history_previous(),
+            KeyCode::Down => app.history_next(),
+            KeyCode::Left => input.left(),
+            KeyCode::Right => input.right(),
+            KeyCode::Home => input.home(),
+            KeyCode::End => input.end(),
+            _ => {}
+        }
+        return false;
+    }
+
+    // `gg` and `G` go to the top and bottom of every list, like Home and End
+    let after_g = std::mem::take(&mut app.pending_g);
+    let key = match key.code {
+        KeyCode::Char('g') if after_g => KeyEvent::from(KeyCode::Home),
+        KeyCode::Char('g') => {
+            app.pending_g = true;
+            return false;
+        }
+        KeyCode::Char('G') => KeyEvent::from(KeyCode::End),
+        _ => key,
+    };
+
+    // Tree navigation in the detail views
+    if let Some(tree) = app.detail_tree_mut() {
+        if handle_tree_key(tree, key) {
+            return false;
+        }
+    }
+
+    // Clipboard, in every view
+    match key.code {
+        KeyCode::Char('y') => {
+            app.yank_id();
+            return false;
+        }
+        KeyCode::Char('Y') => {
+            app.yank_payload();
+            return false;
+        }
+        _ => {}
+    }
+
+    // Handle keys based on current view mode
+    match app.view_mode {
+        ViewMode::List => match key.code {
+            KeyCode::Char('q') => return true,
+            KeyCode::Esc => return true,
+            KeyCode::Char('r') => app.refresh(),
+            KeyCode::Char('P') => app.open_profile_picker(),
+            KeyCode::Char('T') => app.open_prompt(PromptKind::Tenant),
+            KeyCode::Char('s') => app.cycle_focus(),
+            KeyCode::Tab => app.next_tab(),
+            KeyCode::BackTab => app.previous_tab(),
+            KeyCode::Down | KeyCode::Char('j') => app.next_item(),
+            KeyCode::Up | KeyCode::Char('k') => app.previous_item(),
+            KeyCode::PageDown => app.jump(Jump::PageDown),
+            KeyCode::PageUp => app.jump(Jump::PageUp),
+            KeyCode::Home => app.jump(Jump::First),
+            KeyCode::End => app.jump(Jump::Last),
+            KeyCode::Char('1') => app.set_tab(0),
+            KeyCode::Char('2') => app.set_tab(1),
+            KeyCode::Char('3') => app.set_tab(2),
+            KeyCode::Char('4') => app.set_tab(3),
+            KeyCode::Char('f') => {
+                app.cycle_status_filter();
+                app.refresh();
+            }
+            KeyCode::Char(']') => app.next_page(),
+            KeyCode::Char('[') => app.previous_page(),
+            KeyCode::Char('o') => app.cycle_sort(),
+            KeyCode::Char('O') => app.reverse_sort(),
+            KeyCode::Char('h') if app.tab == app::Tab::Metrics => {
+                app.toggle_metrics_granularity();
+                app.refresh();
+            }
+            KeyCode::Char('t') if app.tab == app::Tab::Metrics => app.open_range_picker(),
+            KeyCode::Char('m') if app.tab == app::Tab::Metrics => app.open_tenant_picker(),
+            KeyCode::Enter if app.tab == app::Tab::Instances => {
+                app.open_instance_detail();
+            }
+            KeyCode::Enter if app.tab == app::Tab::Images => app.open_image_metrics(),
+            KeyCode::Char('/') if app.tab == app::Tab::Instances => {
+                app.open_prompt(PromptKind::Search);
+            }
+            KeyCode::Char('F') if app.tab == app::Tab::Instances => {
+                app.open_prompt(PromptKind::Filter);
+            }
+            KeyCode::Char('x') => app.request_action(InstanceAction::Cancel),
+            KeyCode::Char('p') => app.request_action(InstanceAction::Suspend),
+            KeyCode::Char('u') => app.request_action(InstanceAction::Resume),
+            KeyCode::Char('R') => app.request_action(InstanceAction::Rerun),
+            _ => {}
+        },
+        ViewMode::InstanceDetail => match key.code {
+            KeyCode::Esc => app.go_back(),
+            KeyCode::Char('c') => app.open_checkpoints_list(),
+            KeyCode::Char('t') => app.open_timeline(),
+            KeyCode::Char('f') => app.toggle_follow(),
+            KeyCode::Char('/') => app.open_prompt(PromptKind::Query),
+            KeyCode::Char('e') => app.open_prompt(PromptKind::Export),
+            KeyCode::Char('E') => app.open_prompt(PromptKind::ExportBundle),
+            KeyCode::Char('x') => app.request_action(InstanceAction::Cancel),
+            KeyCode::Char('p') => app.request_action(InstanceAction::Suspend),
+            KeyCode::Char('u') => app.request_action(InstanceAction::Resume),
+            KeyCode::Char('R') => app.request_action(InstanceAction::Rerun),
+            _ => {}
+        },
+        ViewMode::CheckpointsList => match key.code {
+            KeyCode::Esc => app.go_back(),
+            KeyCode::Enter => app.open_checkpoint_detail(),
+            KeyCode::Char(' ') => app.toggle_checkpoint_mark(),
+            KeyCode::Char('d') => app.open_checkpoint_diff(),
+            KeyCode::Down | KeyCode::Char('j') => app.next_checkpoint(),
+            KeyCode::Up | KeyCode::Char('k') => app.previous_checkpoint(),
+            KeyCode::PageDown => app.jump(Jump::PageDown),
+            KeyCode::PageUp => app.jump(Jump::PageUp),
+            KeyCode::Home => app.jump(Jump::First),
+            KeyCode::End => app.jump(Jump::Last),
+            KeyCode::Char(']') => app.next_page(),
+            KeyCode::Char('[') => app.previous_page(),
+            KeyCode::Char('o') => app.cycle_sort(),
+            KeyCode::Char('O') => app.reverse_sort(),
+            _ => {}
+        },
+        ViewMode::CheckpointDetail => match key.code {
+            KeyCode::Esc => app.go_back(),
+            KeyCode::Char('/') => app.open_prompt(PromptKind::Query),
+            KeyCode::Char('e') => app.open_prompt(PromptKind::Export),
+            _ => {}
+        },
+        ViewMode::CheckpointDiff => {
+            if key.code == KeyCode::Esc {
+                app.go_back();
+            } else if let Some(diff) = app.checkpoint_diff.as_mut() {
+                match key.code {
+                    KeyCode::Down | KeyCode::Char('j') => diff.next(),
+                    KeyCode::Up | KeyCode::Char('k') => diff.previous(),
+                    KeyCode::PageDown => diff.page_down(),
+                    KeyCode::PageUp => diff.page_up(),
+                    KeyCode::Home => diff.first(),
+                    KeyCode::End => diff.last(),
+                    KeyCode::Char('v') => diff.toggle_layout(),
+                    _ => {}
+                }
+            }
+        }
+        ViewMode::Timeline => {
+            if key.code == KeyCode::Esc {
+                app.go_back();
+            } else if let Some(timeline) = app.timeline.as_mut() {
+                match key.code {
+                    KeyCode::Down | KeyCode::Char('j') => timeline.next(),
+                    KeyCode::Up | KeyCode::Char('k') => timeline.previous(),
+                    KeyCode::PageDown => timeline.page_down(),
+                    KeyCode::PageUp => timeline.page_up(),
+                    KeyCode::Home => timeline.first(),
+                    KeyCode::End => timeline.last(),
+                    KeyCode::Char('s') => timeline.select_slowest(),
+                    _ => {}
+                }
+            }
+        }
+        ViewMode::ImageMetrics => match key.code {
+            KeyCode::Esc => app.go_back(),
+            KeyCode::Down | KeyCode::Char('j') => app.next_image_bucket(),
+            KeyCode::Up | KeyCode::Char('k') => app.previous_image_bucket(),
+            KeyCode::PageDown => app.jump(Jump::PageDown),
+            KeyCode::PageUp => app.jump(Jump::PageUp),
+            KeyCode::Home => app.jump(Jump::First),
+            KeyCode::End => app.jump(Jump::Last),
+            KeyCode::Char('h') => app.toggle_image_metrics_granularity(),
+            KeyCode::Char('t') => app.open_range_picker(),
+            KeyCode::Char('r') => app.fetch_image_metrics(),
+            _ => {}
+        },
+    }
+
+    false
+}
+
+/// Handle a JSON tree key. Returns `true` when the key was used.
+fn handle_tree_key(tree: &mut JsonTree, key: KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => tree.next(),
+        KeyCode::Up | KeyCode::Char('k') => tree.previous(),
+        KeyCode::PageDown => tree.page_down(),
+        KeyCode::PageUp => tree.page_up(),
+        KeyCode::Home => tree.first(),
+        KeyCode::End => tree.last(),
+        KeyCode::Right | KeyCode::Char('l') => tree.expand(),
+        KeyCode::Left | KeyCode::Char('h') => tree.collapse(),
+        KeyCode::Enter | KeyCode::Char(' ') => tree.toggle(),
+        KeyCode::Char('J') => tree.next_sibling(),
+        KeyCode::Char('K') => tree.previous_sibling(),
+        KeyCode::Char('H') => tree.parent(),
+        KeyCode::Char('+') => tree.expand_all(),
+        KeyCode::Char('-') => tree.collapse_all(),
+        _ => return false,
     }
+    true
 }
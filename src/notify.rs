@@ -0,0 +1,15 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Getting the user's attention from the terminal.
+
+use std::io::{self, Write};
+
+/// Ring the terminal bell.
+///
+/// Depending on the terminal this beeps, flashes the window or raises a
+/// desktop notification.
+pub fn bell() -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(b"\x07")?;
+    stdout.flush()
+}
@@ -0,0 +1,211 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! JSON queries for the detail views.
+//!
+//! A query is either JSONPath (RFC 9535), e.g. `$.orders[?@.total > 100].id`,
+//! or a jq-style path starting with `.`, e.g. `.orders[].id`, which is
+//! translated to JSONPath. jq pipes and functions are not supported.
+
+use serde_json::Value;
+use serde_json_path::JsonPath;
+
+/// A parsed query.
+#[derive(Debug, Clone)]
+pub struct Query {
+    /// Text as typed
+    pub text: String,
+    path: JsonPath,
+}
+
+/// A node selected by a query.
+#[derive(Debug, Clone)]
+pub struct Match {
+    /// JSON pointer of the node
+    pub pointer: String,
+    /// Normalized JSONPath of the node, e.g. `$['orders'][0]`
+    pub label: String,
+}
+
+impl Query {
+    /// Parse a JSONPath or jq-style query
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let path = if text.starts_with('.') {
+            let translated = jq_to_jsonpath(text)?;
+            JsonPath::parse(&translated).map_err(|e| format!("Invalid path: {}", e))?
+        } else {
+            JsonPath::parse(text).map_err(|e| format!("Invalid JSONPath: {}", e))?
+        };
+        Ok(Self {
+            text: text.to_string(),
+            path,
+        })
+    }
+
+    /// Nodes of `value` selected by the query, in document order
+    pub fn run(&self, value: &Value) -> Vec<Match> {
+        self.path
+            .query_located(value)
+            .into_iter()
+            .map(|node| Match {
+                pointer: node.location().to_json_pointer(),
+                label: node.location().to_string(),
+            })
+            .collect()
+    }
+}
+
+/// Translate a jq-style path (`.a.b`, `."a b"`, `.[0]`, `.a[]`, `.a[1:3]`,
+/// `.["a"]`, `..a`) to JSONPath
+fn jq_to_jsonpath(text: &str) -> Result<String, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::from("$");
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '.' => {
+                i += 1;
+                if chars.get(i) == Some(&'.') {
+                    // Recursive descent; a member name or bracket must follow
+                    out.push_str("..");
+                    i += 1;
+                    if i >= chars.len() || chars[i] == '.' {
+                        return Err("Expected a name after '..'".to_string());
+                    }
+                    if chars[i] != '[' && chars[i] != '"' {
+                        let name = take_identifier(&chars, &mut i);
+                        out.push_str(&quote_name(&name));
+                    }
+                    continue;
+                }
+                match chars.get(i) {
+                    // `.[...]` or the bare identity `.`
+                    None if out == "$" => {}
+                    Some('[') => {}
+                    None => return Err("Expected a name after '.'".to_string()),
+                    Some('"') => {
+                        let name = take_string(&chars, &mut i)?;
+                        out.push_str(&quote_name(&name));
+                    }
+                    Some(_) => {
+                        let name = take_identifier(&chars, &mut i);
+                        if name.is_empty() {
+                            return Err(format!("Unexpected '{}' after '.'", chars[i]));
+                        }
+                        out.push_str(&quote_name(&name));
+                    }
+                }
+            }
+            '[' => {
+                let end = chars[i..]
+                    .iter()
+                    .position(|&c| c == ']')
+                    .map(|p| i + p)
+                    .ok_or_else(|| "Missing ']'".to_string())?;
+                let inner: String = chars[i + 1..end].iter().collect();
+                let inner = inner.trim();
+                if inner.is_empty() {
+                    out.push_str("[*]");
+                } else if inner.starts_with('"') {
+                    let name: String = serde_json::from_str(inner)
+                        .map_err(|_| format!("Invalid key {}", inner))?;
+                    out.push_str(&quote_name(&name));
+                } else {
+                    out.push_str(&format!("[{}]", inner));
+                }
+                i = end + 1;
+            }
+            '"' => {
+                let name = take_string(&chars, &mut i)?;
+                out.push_str(&quote_name(&name));
+            }
+            // Paths never fail in JSONPath, so jq's `?` changes nothing
+            '?' => i += 1,
+            c if c.is_whitespace() => i += 1,
+            '|' => return Err("jq pipes are not supported; use a JSONPath filter".to_string()),
+            c => return Err(format!("Unexpected '{}' in path", c)),
+        }
+    }
+
+    Ok(out)
+}
+
+/// Read a jq identifier starting at `i`
+fn take_identifier(chars: &[char], i: &mut usize) -> String {
+    let start = *i;
+    while *i < chars.len() && (chars[*i].is_alphanumeric() || chars[*i] == '_') {
+        *i += 1;
+    }
+    chars[start..*i].iter().collect()
+}
+
+/// Read a double-quoted JSON string starting at `i`
+fn take_string(chars: &[char], i: &mut usize) -> Result<String, String> {
+    let start = *i;
+    *i += 1;
+    while *i < chars.len() {
+        match chars[*i] {
+            '\\' => *i += 2,
+            '"' => {
+                *i += 1;
+                let literal: String = chars[start..*i].iter().collect();
+                return serde_json::from_str(&literal)
+                    .map_err(|_| format!("Invalid key {}", literal));
+            }
+            _ => *i += 1,
+        }
+    }
+    Err("Unterminated string".to_string())
+}
+
+/// Bracketed JSONPath member selector for `name`
+fn quote_name(name: &str) -> String {
+    format!("['{}']", name.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn translates_jq_paths() {
+        assert_eq!(jq_to_jsonpath(".").unwrap(), "$");
+        assert_eq!(jq_to_jsonpath(".a.b").unwrap(), "$['a']['b']");
+        assert_eq!(jq_to_jsonpath(".\"a b\"").unwrap(), "$['a b']");
+        assert_eq!(jq_to_jsonpath(".[0]").unwrap(), "$[0]");
+        assert_eq!(jq_to_jsonpath(".a[]").unwrap(), "$['a'][*]");
+        assert_eq!(jq_to_jsonpath(".a[1:3]").unwrap(), "$['a'][1:3]");
+        assert_eq!(jq_to_jsonpath(".[\"a\"]").unwrap(), "$['a']");
+        assert_eq!(jq_to_jsonpath("..a").unwrap(), "$..['a']");
+        assert_eq!(jq_to_jsonpath(".a[]?.b").unwrap(), "$['a'][*]['b']");
+        assert_eq!(jq_to_jsonpath(".\"it's\"").unwrap(), "$['it\\'s']");
+    }
+
+    #[test]
+    fn rejects_unsupported_jq() {
+        assert!(jq_to_jsonpath(".a | length").is_err());
+        assert!(jq_to_jsonpath(".a.").is_err());
+        assert!(jq_to_jsonpath("..").is_err());
+        assert!(jq_to_jsonpath(".a[0").is_err());
+        assert!(jq_to_jsonpath(".\"a").is_err());
+        assert!(jq_to_jsonpath(".-").is_err());
+    }
+
+    #[test]
+    fn runs_both_syntaxes() {
+        let value = json!({"orders": [{"id": 1, "total": 50}, {"id": 2, "total": 150}]});
+        let pointers = |text: &str| -> Vec<String> {
+            Query::parse(text)
+                .unwrap()
+                .run(&value)
+                .into_iter()
+                .map(|m| m.pointer)
+                .collect()
+        };
+        assert_eq!(pointers(".orders[].id"), ["/orders/0/id", "/orders/1/id"]);
+        assert_eq!(pointers("$.orders[?@.total > 100].id"), ["/orders/1/id"]);
+        assert!(Query::parse("$.orders[").is_err());
+    }
+}
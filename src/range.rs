@@ -0,0 +1,226 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Time range of the tenant metrics.
+//!
+//! A range is either the last hour, day, etc. up to each refresh, or fixed
+//! bounds typed as `START..END`.
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+
+use crate::filter::{parse_age, parse_time};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricsRange {
+    /// This long up to now
+    Last(ChronoDuration),
+    /// Fixed bounds; without an end the range reaches up to now
+    Between {
+        start: DateTime<Utc>,
+        end: Option<DateTime<Utc>>,
+    },
+}
+
+impl Default for MetricsRange {
+    /// The last 24 hours, which is also what the server picks on its own
+    fn default() -> Self {
+        MetricsRange::Last(ChronoDuration::hours(24))
+    }
+}
+
+impl MetricsRange {
+    /// Ranges offered in the picker
+    pub fn presets() -> [MetricsRange; 5] {
+        [
+            MetricsRange::Last(ChronoDuration::hours(1)),
+            MetricsRange::Last(ChronoDuration::hours(6)),
+            MetricsRange::Last(ChronoDuration::hours(24)),
+            MetricsRange::Last(ChronoDuration::days(7)),
+            MetricsRange::Last(ChronoDuration::days(30)),
+        ]
+    }
+
+    /// Parse a typed range.
+    ///
+    /// Accepts an age (`12h`) for that long up to now, or `START..END`
+    /// where each side is a time as in [`parse_time`] and the end may be
+    /// `now` or left out. A single time means from then up to now.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        if let Some(age) = parse_age(input) {
+            if age <= ChronoDuration::zero() {
+                return Err(format!("Invalid range '{}'", input));
+            }
+            return Ok(MetricsRange::Last(age));
+        }
+
+        let (start, end) = input.split_once("..").unwrap_or((input, ""));
+        let start = parse_time(start)?;
+        let end = match end.trim() {
+            "" | "now" => None,
+            end => Some(parse_time(end)?),
+        };
+        if start >= end.unwrap_or_else(Utc::now) {
+            return Err("The range must start before it ends".to_string());
+        }
+        Ok(MetricsRange::Between { start, end })
+    }
+
+    /// Start and end of the range as of `now`
+    pub fn bounds(&self, now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        match *self {
+            MetricsRange::Last(length) => (now - length, now),
+            MetricsRange::Between { start, end } => (start, end.unwrap_or(now)),
+        }
+    }
+
+    /// Short description, e.g. `last 24h`
+    pub fn label(&self) -> String {
+        match self {
+            MetricsRange::Last(length) => format!("last {}", format_age(*length)),
+            MetricsRange::Between { start, end: None } => {
+                format!("since {}", start.format("%Y-%m-%d %H:%M"))
+            }
+            MetricsRange::Between {
+                start,
+                end: Some(end),
+            } => format!(
+                "{} – {}",
+                start.format("%Y-%m-%d %H:%M"),
+                end.format("%Y-%m-%d %H:%M")
+            ),
+        }
+    }
+
+    /// The range in the syntax [`MetricsRange::parse`] accepts
+    pub fn text(&self) -> String {
+        match self {
+            MetricsRange::Last(length) => format_age(*length),
+            MetricsRange::Between { start, end } => format!(
+                "{}..{}",
+                start.format("%Y-%m-%d %H:%M"),
+                end.map_or("now".to_string(), |end| end
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string())
+            ),
+        }
+    }
+}
+
+/// `length` in its largest whole unit, e.g. `7d`
+fn format_age(length: ChronoDuration) -> String {
+    let minutes = length.num_minutes();
+    if minutes >= 60 * 24 * 2 && minutes % (60 * 24) == 0 {
+        format!("{}d", minutes / (60 * 24))
+    } else if minutes >= 60 && minutes % 60 == 0 {
+        format!("{}h", minutes / 60)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}s", length.num_seconds())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn parses_relative_ranges() {
+        assert_eq!(
+            MetricsRange::parse(" 12h "),
+            Ok(MetricsRange::Last(ChronoDuration::hours(12)))
+        );
+        assert_eq!(
+            MetricsRange::parse("7d"),
+            Ok(MetricsRange::Last(ChronoDuration::days(7)))
+        );
+        assert!(MetricsRange::parse("0h").is_err());
+        assert!(MetricsRange::parse("-3d").is_err());
+    }
+
+    #[test]
+    fn parses_fixed_ranges() {
+        assert_eq!(
+            MetricsRange::parse("2025-01-01..2025-01-02 12:00"),
+            Ok(MetricsRange::Between {
+                start: at(2025, 1, 1, 0, 0),
+                end: Some(at(2025, 1, 2, 12, 0)),
+            })
+        );
+        let open = MetricsRange::Between {
+            start: at(2025, 1, 1, 0, 0),
+            end: None,
+        };
+        assert_eq!(MetricsRange::parse("2025-01-01..now"), Ok(open));
+        assert_eq!(MetricsRange::parse("2025-01-01.."), Ok(open));
+        assert_eq!(MetricsRange::parse("2025-01-01"), Ok(open));
+    }
+
+    #[test]
+    fn rejects_empty_and_inverted_ranges() {
+        assert!(MetricsRange::parse("2025-01-02..2025-01-01").is_err());
+        assert!(MetricsRange::parse("2025-01-01..2025-01-01").is_err());
+        assert!(MetricsRange::parse("2999-01-01..now").is_err());
+        assert!(MetricsRange::parse("2025-01-01..later").is_err());
+        assert!(MetricsRange::parse("").is_err());
+    }
+
+    #[test]
+    fn bounds_end_at_now_unless_fixed() {
+        let now = at(2025, 3, 1, 12, 0);
+        assert_eq!(
+            MetricsRange::Last(ChronoDuration::hours(6)).bounds(now),
+            (at(2025, 3, 1, 6, 0), now)
+        );
+        let start = at(2025, 1, 1, 0, 0);
+        let end = at(2025, 2, 1, 0, 0);
+        assert_eq!(
+            MetricsRange::Between { start, end: None }.bounds(now),
+            (start, now)
+        );
+        assert_eq!(
+            MetricsRange::Between {
+                start,
+                end: Some(end)
+            }
+            .bounds(now),
+            (start, end)
+        );
+    }
+
+    #[test]
+    fn labels_and_text() {
+        assert_eq!(MetricsRange::default().label(), "last 24h");
+        assert_eq!(MetricsRange::Last(ChronoDuration::days(30)).text(), "30d");
+        assert_eq!(
+            MetricsRange::Last(ChronoDuration::minutes(90)).text(),
+            "90m"
+        );
+        assert_eq!(
+            MetricsRange::Last(ChronoDuration::seconds(45)).text(),
+            "45s"
+        );
+
+        let open = MetricsRange::Between {
+            start: at(2025, 1, 1, 8, 30),
+            end: None,
+        };
+        assert_eq!(open.label(), "since 2025-01-01 08:30");
+        assert_eq!(open.text(), "2025-01-01 08:30..now");
+
+        let fixed = MetricsRange::Between {
+            start: at(2025, 1, 1, 8, 30),
+            end: Some(at(2025, 1, 2, 0, 0)),
+        };
+        assert_eq!(fixed.label(), "2025-01-01 08:30 – 2025-01-02 00:00");
+        assert_eq!(MetricsRange::parse(&fixed.text()), Ok(fixed));
+        for preset in MetricsRange::presets() {
+            assert_eq!(MetricsRange::parse(&preset.text()), Ok(preset));
+        }
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Per-server state for the multi-server dashboard.
+//!
+//! Each monitored `runtara-environment` has its own connection, worker and
+//! list data, so a failing server only marks its own data stale.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use runtara_management_sdk::{HealthStatus, ImageSummary, InstanceSummary, TenantMetricsResult};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::app::LoadState;
+use crate::config::Settings;
+use crate::connection::{self, ConnectionState};
+use crate::worker::{Request, ServerUpdate, Worker};
+
+/// One monitored server.
+pub struct Server {
+    /// Label shown in the server column
+    pub name: String,
+    /// Address as configured
+    pub address: String,
+    /// Whether TLS certificate verification is disabled
+    pub skip_cert_verification: bool,
+
+    /// Background fetcher for this server; `None` when it failed to set up
+    worker: Option<Worker>,
+    pub connection: ConnectionState,
+
+    pub health: Option<HealthStatus>,
+    pub health_state: LoadState,
+
+    pub instances: Vec<InstanceSummary>,
+    pub instances_total: u32,
+    pub instances_state: LoadState,
+
+    /// Running instances with their last heartbeat (or start), from the last
+    /// scan
+    pub running: Vec<(InstanceSummary, DateTime<Utc>)>,
+    /// Running instances in total; only the first page is scanned
+    pub running_total: u32,
+
+    pub images: Vec<ImageSummary>,
+    pub images_total: u32,
+    pub images_state: LoadState,
+
+    pub metrics: Option<TenantMetricsResult>,
+    pub metrics_state: LoadState,
+
+    /// Metrics of the compared tenants
+    pub comparison: Vec<(String, Result<TenantMetricsResult, String>)>,
+    pub comparison_state: LoadState,
+
+    /// Whether a refresh is in flight
+    pub refreshing: bool,
+
+    /// Last error reported for this server
+    pub error: Option<String>,
+}
+
+impl Server {
+    /// Resolve the server address and start connecting.
+    ///
+    /// Updates are tagged with `index`.
+    pub async fn connect(
+        index: usize,
+        name: String,
+        settings: &Settings,
+        updates: UnboundedSender<ServerUpdate>,
+    ) -> Result<Self> {
+        let (config, addrs) = connection::configure(settings).await?;
+        let worker = Worker::new(index, config, &addrs, updates)?;
+
+        Ok(Self::new(name, settings, Some(worker)))
+    }
+
+    /// A server that could not be set up; it is listed with `error` but
+    /// never fetched from
+    pub fn failed(name: String, settings: &Settings, error: String) -> Self {
+        let mut server = Self::new(name, settings, None);
+        server.connection = ConnectionState::Failed {
+            error: error.clone(),
+        };
+        server.finish_refresh();
+        server.error = Some(error);
+        server
+    }
+
+    fn new(name: String, settings: &Settings, worker: Option<Worker>) -> Self {
+        Self {
+            name,
+            address: settings.server.clone(),
+            skip_cert_verification: settings.skip_cert_verification,
+            worker,
+            connection: ConnectionState::Connecting { attempt: 0 },
+            health: None,
+            health_state: LoadState::Loading,
+            instances: Vec::new(),
+            instances_total: 0,
+            instances_state: LoadState::Loading,
+            running: Vec::new(),
+            running_total: 0,
+            images: Vec::new(),
+            images_total: 0,
+            images_state: LoadState::Loading,
+            metrics: None,
+            metrics_state: LoadState::Loading,
+            comparison: Vec::new(),
+            comparison_state: LoadState::Loading,
+            refreshing: false,
+            error: None,
+        }
+    }
+
+    /// Whether the server has a worker to fetch from
+    pub fn usable(&self) -> bool {
+        self.worker.is_some()
+    }
+
+    /// Run a request on this server's worker
+    pub fn send(&self, request: Request) {
+        if let Some(ref worker) = self.worker {
+            worker.send(request);
+        }
+    }
+
+    /// Mark all list data as being fetched; a failed server has nothing to
+    /// fetch
+    pub fn start_refresh(&mut self) {
+        if !self.usable() {
+            return;
+        }
+        self.refreshing = true;
+        self.health_state = LoadState::Loading;
+        self.instances_state = LoadState::Loading;
+        self.images_state = LoadState::Loading;
+        self.metrics_state = LoadState::Loading;
+        self.comparison_state = LoadState::Loading;
+    }
+
+    /// Mark the in-flight refresh as finished; parts that never arrived are
+    /// stale
+    pub fn finish_refresh(&mut self) {
+        self.refreshing = false;
+        for state in [
+            &mut self.health_state,
+            &mut self.instances_state,
+            &mut self.images_state,
+            &mut self.metrics_state,
+            &mut self.comparison_state,
+        ] {
+            if *state == LoadState::Loading {
+                *state = LoadState::Stale;
+            }
+        }
+    }
+
+    /// Last heartbeat (or start) of a running instance, if it was scanned
+    pub fn last_seen(&self, instance_id: &str) -> Option<DateTime<Utc>> {
+        self.running
+            .iter()
+            .find(|(inst, _)| inst.instance_id == instance_id)
+            .map(|(_, at)| *at)
+    }
+
+    /// Name of the image with `image_id`, if it is on the loaded page
+    pub fn image_name(&self, image_id: &str) -> Option<&str> {
+        self.images
+            .iter()
+            .find(|img| img.image_id == image_id)
+            .map(|img| img.name.as_str())
+    }
+}
@@ -0,0 +1,206 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Sorting of the loaded table rows.
+//!
+//! Each table keeps its own [`Sort`]. Rows are sorted on the client, so a
+//! sort applies to the loaded page; without a column they keep the order the
+//! server returned them in. Instances sorted by creation or finish time are
+//! also ordered by the server (see [`Sort::server_order`]), so the sort holds
+//! across pages.
+
+use runtara_management_sdk::ListInstancesOrder;
+use std::cmp::Ordering;
+
+/// A column a table can be sorted by.
+pub trait SortColumn: Copy + PartialEq + 'static {
+    /// Sortable columns, in cycle order
+    const ALL: &'static [Self];
+
+    fn as_str(&self) -> &'static str;
+}
+
+/// Sort column and direction of one table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sort<C> {
+    pub column: Option<C>,
+    pub descending: bool,
+}
+
+impl<C> Default for Sort<C> {
+    fn default() -> Self {
+        Self {
+            column: None,
+            descending: false,
+        }
+    }
+}
+
+impl<C: SortColumn> Sort<C> {
+    /// Sort by the next column, ascending; after the last, by none
+    pub fn cycle(&mut self) {
+        let next = match self.column {
+            None => C::ALL.first(),
+            Some(column) => C::ALL
+                .iter()
+                .position(|c| *c == column)
+                .and_then(|i| C::ALL.get(i + 1)),
+        };
+        self.column = next.copied();
+        self.descending = false;
+    }
+
+    /// Flip the direction
+    pub fn reverse(&mut self) {
+        self.descending = !self.descending;
+    }
+
+    /// Arrow to put after the header of `column`
+    pub fn arrow(&self, column: C) -> &'static str {
+        match self.column {
+            Some(c) if c == column && self.descending => " ▼",
+            Some(c) if c == column => " ▲",
+            _ => "",
+        }
+    }
+
+    /// Sort `rows` with `compare`, which orders two rows by a column
+    /// ascending. The sort is stable, so ties keep the server order.
+    pub fn apply<T>(&self, rows: &mut [T], compare: impl Fn(C, &T, &T) -> Ordering) {
+        let Some(column) = self.column else {
+            return;
+        };
+        rows.sort_by(|a, b| {
+            let ordering = compare(column, a, b);
+            if self.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+}
+
+/// Sortable columns of the instances table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstanceColumn {
+    Status,
+    Tenant,
+    Image,
+    Created,
+    Finished,
+    Duration,
+}
+
+impl SortColumn for InstanceColumn {
+    const ALL: &'static [Self] = &[
+        InstanceColumn::Status,
+        InstanceColumn::Tenant,
+        InstanceColumn::Image,
+        InstanceColumn::Created,
+        InstanceColumn::Finished,
+        InstanceColumn::Duration,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            InstanceColumn::Status => "Status",
+            InstanceColumn::Tenant => "Tenant",
+            InstanceColumn::Image => "Image",
+            InstanceColumn::Created => "Created",
+            InstanceColumn::Finished => "Finished",
+            InstanceColumn::Duration => "Duration",
+        }
+    }
+}
+
+impl Sort<InstanceColumn> {
+    /// Order to list instances in, for columns the server can order by
+    pub fn server_order(&self) -> Option<ListInstancesOrder> {
+        match (self.column?, self.descending) {
+            (InstanceColumn::Created, false) => Some(ListInstancesOrder::CreatedAtAsc),
+            (InstanceColumn::Created, true) => Some(ListInstancesOrder::CreatedAtDesc),
+            (InstanceColumn::Finished, false) => Some(ListInstancesOrder::FinishedAtAsc),
+            (InstanceColumn::Finished, true) => Some(ListInstancesOrder::FinishedAtDesc),
+            _ => None,
+        }
+    }
+}
+
+/// Sortable columns of the images table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageColumn {
+    Name,
+    Runner,
+    Created,
+}
+
+impl SortColumn for ImageColumn {
+    const ALL: &'static [Self] = &[ImageColumn::Name, ImageColumn::Runner, ImageColumn::Created];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            ImageColumn::Name => "Name",
+            ImageColumn::Runner => "Runner",
+            ImageColumn::Created => "Created",
+        }
+    }
+}
+
+/// Sortable columns of the checkpoints table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointColumn {
+    Time,
+    Size,
+}
+
+impl SortColumn for CheckpointColumn {
+    const ALL: &'static [Self] = &[CheckpointColumn::Time, CheckpointColumn::Size];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            CheckpointColumn::Time => "Created At",
+            CheckpointColumn::Size => "Size",
+        }
+    }
+}
+
+/// Sortable columns of the metrics table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricsColumn {
+    Time,
+    Invocations,
+    Failed,
+    SuccessRate,
+    Duration,
+    Memory,
+}
+
+impl SortColumn for MetricsColumn {
+    const ALL: &'static [Self] = &[
+        MetricsColumn::Time,
+        MetricsColumn::Invocations,
+        MetricsColumn::Failed,
+        MetricsColumn::SuccessRate,
+        MetricsColumn::Duration,
+        MetricsColumn::Memory,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            MetricsColumn::Time => "Time",
+            MetricsColumn::Invocations => "Invocations",
+            MetricsColumn::Failed => "Failed",
+            MetricsColumn::SuccessRate => "Success %",
+            MetricsColumn::Duration => "Avg Duration",
+            MetricsColumn::Memory => "Avg Memory",
+        }
+    }
+}
+
+/// Order of two optional floats; missing values first
+pub fn compare_f64(a: Option<f64>, b: Option<f64>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (a, b) => a.is_some().cmp(&b.is_some()),
+    }
+}
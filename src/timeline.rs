@@ -0,0 +1,219 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Timeline of one instance.
+//!
+//! Lifecycle timestamps and checkpoints are merged into one list of
+//! [`Event`]s in time order. Every step ends at an event and its length is
+//! the gap since the previous step, so slow steps stand out. Heartbeats are
+//! shown as markers and do not split steps.
+
+use chrono::{DateTime, Duration, Utc};
+use runtara_management_sdk::{CheckpointSummary, InstanceInfo, InstanceStatus};
+
+use crate::app::Viewport;
+
+#[derive(Debug, Clone)]
+pub enum EventKind {
+    Created,
+    Started,
+    Checkpoint {
+        id: String,
+        size: u64,
+    },
+    /// Last heartbeat from the executor
+    Heartbeat,
+    Finished(InstanceStatus),
+    /// End of the axis for an instance that has not finished
+    Now,
+}
+
+/// A point on the timeline.
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub at: DateTime<Utc>,
+    pub kind: EventKind,
+    /// Length of the step ending here; `None` for the first event and
+    /// heartbeats
+    pub gap: Option<Duration>,
+}
+
+/// Timeline view of one instance.
+#[derive(Debug)]
+pub struct Timeline {
+    pub instance_id: String,
+    pub status: InstanceStatus,
+    pub retry_count: u32,
+    pub max_retries: u32,
+    pub events: Vec<Event>,
+    /// Start and end of the time axis
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// Time since the last sign of life of a running instance, when it
+    /// exceeds the staleness threshold
+    pub stale_heartbeat: Option<Duration>,
+    selected: usize,
+    view: Viewport,
+}
+
+impl Timeline {
+    /// Build the timeline of `info` with its `checkpoints` as of `now`
+    pub fn new(
+        info: &InstanceInfo,
+        checkpoints: &[CheckpointSummary],
+        now: DateTime<Utc>,
+        stale_after: Duration,
+    ) -> Self {
+        let mut events = vec![Event {
+            at: info.created_at,
+            kind: EventKind::Created,
+            gap: None,
+        }];
+        if let Some(at) = info.started_at {
+            events.push(Event {
+                at,
+                kind: EventKind::Started,
+                gap: None,
+            });
+        }
+        events.extend(checkpoints.iter().map(|cp| Event {
+            at: cp.created_at,
+            kind: EventKind::Checkpoint {
+                id: cp.checkpoint_id.clone(),
+                size: cp.data_size_bytes,
+            },
+            gap: None,
+        }));
+        if let Some(at) = info.heartbeat_at {
+            events.push(Event {
+                at,
+                kind: EventKind::Heartbeat,
+                gap: None,
+            });
+        }
+        events.push(match info.finished_at {
+            Some(at) => Event {
+                at,
+                kind: EventKind::Finished(info.status),
+                gap: None,
+            },
+            None => Event {
+                at: now,
+                kind: EventKind::Now,
+                gap: None,
+            },
+        });
+        // Stable, so events at the same instant keep their lifecycle order
+        events.sort_by_key(|event| event.at);
+
+        let mut previous: Option<DateTime<Utc>> = None;
+        for event in &mut events {
+            if matches!(event.kind, EventKind::Heartbeat) {
+                continue;
+            }
+            event.gap = previous.map(|at| event.at - at);
+            previous = Some(event.at);
+        }
+
+        let stale_heartbeat = (info.status == InstanceStatus::Running)
+            .then(|| info.heartbeat_at.or(info.started_at))
+            .flatten()
+            .map(|at| now - at)
+            .filter(|age| *age > stale_after);
+
+        let start = events.first().map_or(now, |e| e.at);
+        let end = events.last().map_or(now, |e| e.at);
+        Self {
+            instance_id: info.instance_id.clone(),
+            status: info.status,
+            retry_count: info.retry_count,
+            max_retries: info.max_retries,
+            events,
+            start,
+            end,
+            stale_heartbeat,
+            selected: 0,
+            view: Viewport::default(),
+        }
+    }
+
+    /// Time from the first to the last event
+    pub fn total(&self) -> Duration {
+        self.end - self.start
+    }
+
+    /// Length of the longest step
+    pub fn slowest(&self) -> Option<Duration> {
+        self.events.iter().filter_map(|event| event.gap).max()
+    }
+
+    /// Number of checkpoints on the timeline
+    pub fn checkpoint_count(&self) -> usize {
+        self.events
+            .iter()
+            .filter(|event| matches!(event.kind, EventKind::Checkpoint { .. }))
+            .count()
+    }
+
+    /// Position of `at` on an axis `width` cells wide
+    pub fn position(&self, at: DateTime<Utc>, width: usize) -> usize {
+        let total = self.total().num_milliseconds();
+        if total <= 0 || width == 0 {
+            return 0;
+        }
+        let elapsed = (at - self.start).num_milliseconds().clamp(0, total);
+        ((elapsed as f64 / total as f64) * (width - 1) as f64).round() as usize
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Events to draw in a view `height` rows high, scrolled so the cursor
+    /// is visible
+    pub fn visible_events(&self, height: usize) -> (usize, &[Event]) {
+        let offset = self.view.scroll(self.selected, self.events.len(), height);
+        let end = (offset + height).min(self.events.len());
+        (offset, &self.events[offset.min(end)..end])
+    }
+
+    pub fn next(&mut self) {
+        self.select(self.selected + 1);
+    }
+
+    pub fn previous(&mut self) {
+        self.select(self.selected.saturating_sub(1));
+    }
+
+    pub fn page_down(&mut self) {
+        self.select(self.selected + self.view.page());
+    }
+
+    pub fn page_up(&mut self) {
+        self.select(self.selected.saturating_sub(self.view.page()));
+    }
+
+    pub fn first(&mut self) {
+        self.select(0);
+    }
+
+    pub fn last(&mut self) {
+        self.select(self.events.len().saturating_sub(1));
+    }
+
+    /// Select the longest step
+    pub fn select_slowest(&mut self) {
+        let slowest = self
+            .events
+            .iter()
+            .enumerate()
+            .filter_map(|(i, event)| event.gap.map(|gap| (i, gap)))
+            .max_by_key(|(_, gap)| *gap);
+        if let Some((index, _)) = slowest {
+            self.select(index);
+        }
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.events.len().saturating_sub(1));
+    }
+}
@@ -5,16 +5,72 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    symbols::Marker,
     text::{Line, Span, Text},
-    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, Tabs, Wrap},
+    widgets::{
+        Axis, Block, Borders, Cell, Chart, Clear, Dataset, GraphType, Padding, Paragraph, Row,
+        Scrollbar, ScrollbarOrientation, ScrollbarState, Sparkline, SparklineBar, Table, Tabs,
+        Wrap,
+    },
     Frame,
 };
 
-use crate::app::{format_datetime, format_duration, status_style, App, Tab, ViewMode};
-use runtara_management_sdk::MetricsGranularity;
+use crate::app::{
+    format_bytes, format_datetime, format_duration, status_style, App, Follow, FollowEvent,
+    LoadState, PendingAction, Prompt, PromptKind, StatusFilter, Tab, TenantChoice, TenantPicker,
+    ViewMode, Viewport,
+};
+use crate::compare::TenantTotals;
+use crate::connection::ConnectionState;
+use crate::diff::{preview, ChangeKind};
+use crate::json_tree::{container_summary, JsonTree, Key, RowKind, TreeRow};
+use crate::range::MetricsRange;
+use crate::server::Server;
+use crate::sort::{CheckpointColumn, ImageColumn, InstanceColumn, MetricsColumn, Sort, SortColumn};
+use crate::timeline::{Event, EventKind, Timeline};
+use chrono::{DateTime, Utc};
+use runtara_management_sdk::{
+    Checkpoint, InstanceInfo, InstanceStatus, MetricsBucket, MetricsGranularity,
+    TenantMetricsResult,
+};
 
 /// Main draw function
 pub fn draw(f: &mut Frame, app: &App) {
+    let mut area = f.area();
+
+    // Warning banner while certificates are not verified
+    let unverified: Vec<&str> = app
+        .servers
+        .iter()
+        .filter(|s| s.skip_cert_verification)
+        .map(|s| s.name.as_str())
+        .collect();
+    if !unverified.is_empty() {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(area);
+        let text = if app.multi_server() {
+            format!(
+                " ⚠ TLS certificate verification is disabled for {}: the server identity is not checked ",
+                unverified.join(", ")
+            )
+        } else {
+            " ⚠ TLS certificate verification is disabled: the server's identity is not checked "
+                .to_string()
+        };
+        let warning = Paragraph::new(Line::from(Span::styled(
+            text,
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Red)
+                .add_modifier(Modifier::BOLD),
+        )))
+        .style(Style::default().bg(Color::Red));
+        f.render_widget(warning, chunks[0]);
+        area = chunks[1];
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -22,7 +78,7 @@ pub fn draw(f: &mut Frame, app: &App) {
             Constraint::Min(0),    // Content
             Constraint::Length(3), // Footer
         ])
-        .split(f.area());
+        .split(area);
 
     draw_header(f, app, chunks[0]);
     draw_content(f, app, chunks[1]);
@@ -40,6 +96,44 @@ pub fn draw(f: &mut Frame, app: &App) {
         ViewMode::CheckpointDetail => {
             draw_checkpoint_detail_modal(f, app);
         }
+        ViewMode::CheckpointDiff => {
+            draw_checkpoint_diff_modal(f, app);
+        }
+        ViewMode::Timeline => {
+            draw_timeline_modal(f, app);
+        }
+        ViewMode::ImageMetrics => {
+            draw_image_metrics_modal(f, app);
+        }
+    }
+
+    // Draw profile switcher if open
+    if let Some(selected) = app.profile_picker {
+        draw_profile_picker(f, app, selected);
+    }
+
+    // Draw tenant selector if open
+    if let Some(prompt) = app
+        .prompt
+        .as_ref()
+        .filter(|prompt| prompt.kind == PromptKind::Tenant)
+    {
+        draw_tenant_selector(f, app, prompt);
+    }
+
+    // Draw tenant picker if open
+    if let Some(ref picker) = app.tenant_picker {
+        draw_tenant_picker(f, app, picker);
+    }
+
+    // Draw metrics range picker if open
+    if let Some(selected) = app.range_picker {
+        draw_range_picker(f, app, selected);
+    }
+
+    // Draw confirmation dialog if an action is pending
+    if let Some(ref pending) = app.pending_action {
+        draw_confirm_popup(f, pending);
     }
 
     // Draw error popup if present
@@ -69,12 +163,41 @@ fn draw_header(f: &mut Frame, app: &App, area: Rect) {
         })
         .collect();
 
+    let title = match app.profile {
+        Some(ref profile) => format!(" Runtara Monitor [{}] ", profile),
+        None => " Runtara Monitor ".to_string(),
+    };
+    let scope = match app.tenant_id {
+        Some(ref tenant) => Span::styled(
+            format!(" Tenant: {} ", tenant),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        None => Span::styled(" All tenants ", Style::default().fg(Color::DarkGray)),
+    };
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title(scope);
+    if let Some(ref name) = app.switching_profile {
+        block = block.title(Span::styled(
+            format!(" Switching to {}… ", name),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    let (stuck, partial) = app.stuck_count();
+    if stuck > 0 {
+        block = block.title_top(
+            Line::from(Span::styled(
+                format!(" ⚠ {}{} stuck ", stuck, if partial { "+" } else { "" }),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ))
+            .right_aligned(),
+        );
+    }
     let tabs = Tabs::new(titles)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" Runtara Monitor "),
-        )
+        .block(block)
         .highlight_style(Style::default().fg(Color::Yellow))
         .select(match app.tab {
             Tab::Instances => 0,
@@ -86,18 +209,64 @@ fn draw_header(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(tabs, chunks[0]);
 
     // Connection status
-    let status_text = if app.connected {
-        Span::styled(" Connected ", Style::default().fg(Color::Green))
+    let status = if app.multi_server() {
+        let connected = app
+            .servers
+            .iter()
+            .filter(|s| matches!(s.connection, ConnectionState::Connected { .. }))
+            .count();
+        let color = if connected == app.servers.len() {
+            Color::Green
+        } else if connected == 0 {
+            Color::Red
+        } else {
+            Color::Yellow
+        };
+        Line::from(vec![
+            Span::raw("Servers: "),
+            Span::styled(
+                format!(" {}/{} connected ", connected, app.servers.len()),
+                Style::default().fg(color),
+            ),
+        ])
     } else {
-        Span::styled(" Disconnected ", Style::default().fg(Color::Red))
+        Line::from(vec![
+            Span::raw("Status: "),
+            connection_span(&app.servers[0].connection),
+        ])
     };
 
-    let status = Paragraph::new(Line::from(vec![Span::raw("Status: "), status_text]))
-        .block(Block::default().borders(Borders::ALL));
+    let status = Paragraph::new(status).block(Block::default().borders(Borders::ALL));
 
     f.render_widget(status, chunks[1]);
 }
 
+/// Short connection state label
+fn connection_span(state: &ConnectionState) -> Span<'static> {
+    match state {
+        ConnectionState::Connected { .. } => {
+            Span::styled(" Connected ", Style::default().fg(Color::Green))
+        }
+        ConnectionState::Connecting { attempt: 0 } => {
+            Span::styled(" Connecting… ", Style::default().fg(Color::Yellow))
+        }
+        ConnectionState::Connecting { attempt } => Span::styled(
+            format!(" Reconnecting #{} ", attempt + 1),
+            Style::default().fg(Color::Yellow),
+        ),
+        ConnectionState::Waiting { retry_at, .. } => Span::styled(
+            format!(
+                " Retry in {}s ",
+                retry_at
+                    .saturating_duration_since(std::time::Instant::now())
+                    .as_secs()
+            ),
+            Style::default().fg(Color::Red),
+        ),
+        ConnectionState::Failed { .. } => Span::styled(" Failed ", Style::default().fg(Color::Red)),
+    }
+}
+
 fn draw_content(f: &mut Frame, app: &App, area: Rect) {
     match app.tab {
         Tab::Instances => draw_instances(f, app, area),
@@ -113,50 +282,104 @@ fn draw_instances(f: &mut Frame, app: &App, area: Rect) {
         .constraints([Constraint::Length(1), Constraint::Min(0)])
         .split(area);
 
-    // Filter info
-    let filter_info = Paragraph::new(Line::from(vec![
-        Span::raw(" Filter: "),
-        Span::styled(
-            app.status_filter.as_str(),
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::raw(" | "),
-        Span::raw(format!("Total: {} ", app.instances_total)),
-        Span::raw("| Press 'f' to cycle filter"),
-    ]));
-    f.render_widget(filter_info, chunks[0]);
+    // Filter info, or the open prompt
+    let list_prompt = app.prompt.as_ref().and_then(|prompt| match prompt.kind {
+        PromptKind::Search => Some((" Search this page /", prompt)),
+        PromptKind::Filter => Some((" Filter> ", prompt)),
+        PromptKind::Query
+        | PromptKind::Export
+        | PromptKind::ExportBundle
+        | PromptKind::MetricsRange
+        | PromptKind::Tenant => None,
+    });
+    if let Some((label, prompt)) = list_prompt {
+        let prompt_line = Paragraph::new(Line::from(vec![
+            Span::styled(label, Style::default().fg(Color::Yellow)),
+            Span::raw(prompt.input.value()),
+        ]));
+        f.render_widget(prompt_line, chunks[0]);
+
+        let cursor_x = chunks[0].x + (label.chars().count() + prompt.input.cursor()) as u16;
+        f.set_cursor_position((
+            cursor_x.min(chunks[0].right().saturating_sub(1)),
+            chunks[0].y,
+        ));
+    } else {
+        let highlight = Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD);
+        let mut spans = vec![
+            Span::raw(" Filter: "),
+            Span::styled(app.status_filter.as_str(), highlight),
+        ];
+        if !app.instance_filter.search.is_empty() {
+            spans.push(Span::raw(" | Search (this page): "));
+            spans.push(Span::styled(app.instance_filter.search.clone(), highlight));
+        }
+        if app.instance_filter.has_server_filters() {
+            spans.push(Span::raw(" | "));
+            spans.push(Span::styled(app.instance_filter.bar_text(), highlight));
+        }
+        spans.push(Span::raw(format!(" | Total: {} ", app.instances_total())));
+        spans.push(Span::raw("| f:Status /:Search F:Filter bar"));
+        f.render_widget(Paragraph::new(Line::from(spans)), chunks[0]);
+    }
 
-    // Instances table
-    let header = Row::new(vec![
+    // Instances table, with a server column when several are monitored
+    let with_server = app.multi_server();
+    let mut header = vec![
         Cell::from("Instance ID").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Status").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Tenant").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Image").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Created").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Finished").style(Style::default().add_modifier(Modifier::BOLD)),
-    ])
-    .height(1)
-    .style(Style::default().fg(Color::Yellow));
+        sort_header(&app.instance_sort, InstanceColumn::Status),
+        sort_header(&app.instance_sort, InstanceColumn::Tenant),
+        sort_header(&app.instance_sort, InstanceColumn::Image),
+        sort_header(&app.instance_sort, InstanceColumn::Created),
+        sort_header(&app.instance_sort, InstanceColumn::Finished),
+        sort_header(&app.instance_sort, InstanceColumn::Duration),
+    ];
+    if with_server {
+        header.insert(
+            0,
+            Cell::from("Server").style(Style::default().add_modifier(Modifier::BOLD)),
+        );
+    }
+    let header = Row::new(header)
+        .height(1)
+        .style(Style::default().fg(Color::Yellow));
 
-    let rows: Vec<Row> = app
-        .instances
+    let visible = app.visible_instances();
+    let rows: Vec<Row> = visible
         .iter()
-        .enumerate()
-        .map(|(i, inst)| {
+        .map(|&(server, inst)| {
             let (status_text, status_color) = status_style(inst.status);
-            let is_selected = i == app.instances_selected;
 
-            let style = if is_selected {
-                Style::default().bg(Color::DarkGray)
-            } else {
-                Style::default()
+            // Running instances show how long ago they were last seen
+            let last_seen = (inst.status == InstanceStatus::Running)
+                .then(|| app.servers[server].last_seen(&inst.instance_id))
+                .flatten();
+            let status_cell = match last_seen {
+                Some(at) => {
+                    let age = format_duration(
+                        (Utc::now() - at).to_std().unwrap_or_default().as_millis() as u64,
+                    );
+                    if app.is_stale(at) {
+                        let stale = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+                        Cell::from(Line::from(vec![
+                            Span::styled("Stale", stale),
+                            Span::styled(format!(" {}", age), stale),
+                        ]))
+                    } else {
+                        Cell::from(Line::from(vec![
+                            Span::styled(status_text, Style::default().fg(status_color)),
+                            Span::styled(format!(" {}", age), Style::default().fg(Color::DarkGray)),
+                        ]))
+                    }
+                }
+                None => Cell::from(status_text).style(Style::default().fg(status_color)),
             };
 
-            Row::new(vec![
+            let mut cells = vec![
                 Cell::from(truncate(&inst.instance_id, 36)),
-                Cell::from(status_text).style(Style::default().fg(status_color)),
+                status_cell,
                 Cell::from(truncate(&inst.tenant_id, 20)),
                 Cell::from(truncate(&inst.image_id, 20)),
                 Cell::from(format_datetime(&inst.created_at)),
@@ -166,88 +389,128 @@ fn draw_instances(f: &mut Frame, app: &App, area: Rect) {
                         .map(format_datetime)
                         .unwrap_or_else(|| "-".to_string()),
                 ),
-            ])
-            .style(style)
+                Cell::from(
+                    inst.started_at
+                        .map(|started| {
+                            let end = inst.finished_at.unwrap_or_else(Utc::now);
+                            format_duration((end - started).num_milliseconds().max(0) as u64)
+                        })
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+            ];
+            if with_server {
+                cells.insert(0, server_cell(app, server));
+            }
+            Row::new(cells)
         })
         .collect();
 
-    let table = Table::new(
-        rows,
-        [
-            Constraint::Length(38),
-            Constraint::Length(12),
-            Constraint::Length(22),
-            Constraint::Length(22),
-            Constraint::Length(20),
-            Constraint::Length(20),
-        ],
-    )
-    .header(header)
-    .block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(format!(" Instances ({}) ", app.instances.len())),
+    let mut widths = vec![
+        Constraint::Length(38),
+        Constraint::Length(16),
+        Constraint::Length(22),
+        Constraint::Length(22),
+        Constraint::Length(20),
+        Constraint::Length(20),
+        Constraint::Length(10),
+    ];
+    if with_server {
+        widths.insert(0, Constraint::Length(SERVER_WIDTH + 2));
+    }
+
+    let page_len = if app.status_filter == StatusFilter::Stuck {
+        visible.len()
+    } else {
+        app.shown_servers().map(|(_, s)| s.instances.len()).sum()
+    };
+    let len = rows.len();
+    let table = Table::new(rows, widths).header(header).block(
+        Block::default().borders(Borders::ALL).title(format!(
+            " Instances ({}){}{}{} ",
+            rows_label(app.instances_offset, page_len, app.instances_total()),
+            if app.instance_filter.search.is_empty() {
+                String::new()
+            } else {
+                format!(", {} matching on this page", visible.len())
+            },
+            focus_suffix(app),
+            state_suffix(app.instances_state())
+        )),
     );
 
-    f.render_widget(table, chunks[1]);
+    draw_table(
+        f,
+        table,
+        chunks[1],
+        &app.instances_view,
+        app.instances_selected,
+        len,
+    );
 }
 
 fn draw_images(f: &mut Frame, app: &App, area: Rect) {
-    let header = Row::new(vec![
+    let with_server = app.multi_server();
+    let mut header = vec![
         Cell::from("Image ID").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Name").style(Style::default().add_modifier(Modifier::BOLD)),
+        sort_header(&app.image_sort, ImageColumn::Name),
         Cell::from("Tenant").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Runner").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Created").style(Style::default().add_modifier(Modifier::BOLD)),
+        sort_header(&app.image_sort, ImageColumn::Runner),
+        sort_header(&app.image_sort, ImageColumn::Created),
         Cell::from("Description").style(Style::default().add_modifier(Modifier::BOLD)),
-    ])
-    .height(1)
-    .style(Style::default().fg(Color::Yellow));
+    ];
+    if with_server {
+        header.insert(
+            0,
+            Cell::from("Server").style(Style::default().add_modifier(Modifier::BOLD)),
+        );
+    }
+    let header = Row::new(header)
+        .height(1)
+        .style(Style::default().fg(Color::Yellow));
 
     let rows: Vec<Row> = app
-        .images
-        .iter()
-        .enumerate()
-        .map(|(i, img)| {
-            let is_selected = i == app.images_selected;
-
-            let style = if is_selected {
-                Style::default().bg(Color::DarkGray)
-            } else {
-                Style::default()
-            };
-
-            Row::new(vec![
+        .visible_images()
+        .into_iter()
+        .map(|(server, img)| {
+            let mut cells = vec![
                 Cell::from(truncate(&img.image_id, 36)),
                 Cell::from(truncate(&img.name, 30)),
                 Cell::from(truncate(&img.tenant_id, 20)),
                 Cell::from(format!("{:?}", img.runner_type)),
                 Cell::from(format_datetime(&img.created_at)),
                 Cell::from(truncate(img.description.as_deref().unwrap_or("-"), 30)),
-            ])
-            .style(style)
+            ];
+            if with_server {
+                cells.insert(0, server_cell(app, server));
+            }
+            Row::new(cells)
         })
         .collect();
 
-    let table = Table::new(
-        rows,
-        [
-            Constraint::Length(38),
-            Constraint::Length(32),
-            Constraint::Length(22),
-            Constraint::Length(10),
-            Constraint::Length(20),
-            Constraint::Min(20),
-        ],
-    )
-    .header(header)
-    .block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(format!(" Images ({}) ", app.images.len())),
+    let mut widths = vec![
+        Constraint::Length(38),
+        Constraint::Length(32),
+        Constraint::Length(22),
+        Constraint::Length(10),
+        Constraint::Length(20),
+        Constraint::Min(20),
+    ];
+    if with_server {
+        widths.insert(0, Constraint::Length(SERVER_WIDTH + 2));
+    }
+
+    let page_len = app.shown_servers().map(|(_, s)| s.images.len()).sum();
+    let len = rows.len();
+    let table = Table::new(rows, widths).header(header).block(
+        Block::default().borders(Borders::ALL).title(format!(
+            " Images ({}){}{} ",
+            rows_label(app.images_offset, page_len, app.images_total()),
+            focus_suffix(app),
+            state_suffix(app.images_state())
+        )),
     );
 
-    f.render_widget(table, area);
+    draw_table(f, table, area, &app.images_view, app.images_selected, len);
 }
 
 fn draw_metrics(f: &mut Frame, app: &App, area: Rect) {
@@ -262,98 +525,126 @@ fn draw_metrics(f: &mut Frame, app: &App, area: Rect) {
         MetricsGranularity::Daily => "Daily",
     };
 
-    let tenant_text = app
-        .tenant_id
+    let tenant_text = if !app.compare_tenants.is_empty() {
+        format!("Comparing: {}", app.compare_tenants.join(", "))
+    } else {
+        app.metrics_tenant()
+            .map(|t| format!("Tenant: {}", t))
+            .unwrap_or_else(|| "No tenant selected".to_string())
+    };
+
+    let highlight = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    match app
+        .prompt
         .as_ref()
-        .map(|t| format!("Tenant: {}", t))
-        .unwrap_or_else(|| "No tenant selected (use -t flag)".to_string());
+        .filter(|prompt| prompt.kind == PromptKind::MetricsRange)
+    {
+        Some(prompt) => {
+            let label = " Range> ";
+            let prompt_line = Paragraph::new(Line::from(vec![
+                Span::styled(label, Style::default().fg(Color::Yellow)),
+                Span::raw(prompt.input.value()),
+            ]));
+            f.render_widget(prompt_line, chunks[0]);
+
+            let cursor_x = chunks[0].x + (label.chars().count() + prompt.input.cursor()) as u16;
+            f.set_cursor_position((
+                cursor_x.min(chunks[0].right().saturating_sub(1)),
+                chunks[0].y,
+            ));
+        }
+        None => {
+            let filter_info = Paragraph::new(Line::from(vec![
+                Span::raw(" Granularity: "),
+                Span::styled(granularity_text, highlight),
+                Span::raw(" | Range: "),
+                Span::styled(app.metrics_range.label(), highlight),
+                Span::raw(" | "),
+                Span::styled(tenant_text, Style::default().fg(Color::White)),
+                Span::raw(" | h:Granularity t:Range m:Tenants"),
+            ]));
+            f.render_widget(filter_info, chunks[0]);
+        }
+    }
 
-    let filter_info = Paragraph::new(Line::from(vec![
-        Span::raw(" Granularity: "),
-        Span::styled(
-            granularity_text,
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::raw(" | "),
-        Span::styled(tenant_text, Style::default().fg(Color::White)),
-        Span::raw(" | Press 'g' to toggle granularity"),
-    ]));
-    f.render_widget(filter_info, chunks[0]);
+    if !app.compare_tenants.is_empty() {
+        draw_metrics_comparison(f, app, chunks[1]);
+        return;
+    }
 
     // Check if we have metrics data
-    let metrics = match &app.metrics {
+    let metrics = match app.metrics() {
         Some(m) => m,
         None => {
             let no_data = Paragraph::new(vec![
                 Line::from(""),
                 Line::from(Span::styled(
-                    if app.tenant_id.is_none() {
-                        "  Please specify a tenant ID to view metrics"
+                    if app.metrics_tenant().is_none() {
+                        "  Please pick a tenant to view metrics"
+                    } else if app.metrics_server().metrics_state == LoadState::Loading {
+                        "  Loading metrics…"
                     } else {
                         "  No metrics data available"
                     },
                     Style::default().fg(Color::Yellow),
                 )),
                 Line::from(""),
-                Line::from(if app.tenant_id.is_none() {
-                    "  Run with: runtara-tui -t <tenant_id>"
+                Line::from(if app.metrics_tenant().is_none() {
+                    "  Press 'm' to pick one of the tenants seen, or 'T' to scope everything to a tenant"
                 } else {
                     "  Press 'r' to refresh"
                 }),
             ])
-            .block(Block::default().borders(Borders::ALL).title(" Metrics "));
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" Metrics: {} ", app.metrics_range.label())),
+            );
             f.render_widget(no_data, chunks[1]);
             return;
         }
     };
 
+    // Charts above the table when there is room for both
+    let table_area = if chunks[1].height >= METRICS_CHARTS_MIN_HEIGHT && !metrics.buckets.is_empty()
+    {
+        let parts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(METRICS_CHART_HEIGHT), Constraint::Min(0)])
+            .split(chunks[1]);
+        draw_metrics_charts(f, app, metrics, parts[0]);
+        parts[1]
+    } else {
+        chunks[1]
+    };
+
     // Metrics table
     let header = Row::new(vec![
-        Cell::from("Time").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Invocations").style(Style::default().add_modifier(Modifier::BOLD)),
+        sort_header(&app.metrics_sort, MetricsColumn::Time),
+        sort_header(&app.metrics_sort, MetricsColumn::Invocations),
         Cell::from("Success").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Failed").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Success %").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Avg Duration").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Avg Memory").style(Style::default().add_modifier(Modifier::BOLD)),
+        sort_header(&app.metrics_sort, MetricsColumn::Failed),
+        sort_header(&app.metrics_sort, MetricsColumn::SuccessRate),
+        sort_header(&app.metrics_sort, MetricsColumn::Duration),
+        sort_header(&app.metrics_sort, MetricsColumn::Memory),
     ])
     .height(1)
     .style(Style::default().fg(Color::Yellow));
 
-    let rows: Vec<Row> = metrics
-        .buckets
-        .iter()
-        .enumerate()
-        .map(|(i, bucket)| {
-            let is_selected = i == app.metrics_selected;
-
-            let style = if is_selected {
-                Style::default().bg(Color::DarkGray)
-            } else {
-                Style::default()
-            };
-
-            let time_format = match app.metrics_granularity {
-                MetricsGranularity::Hourly => bucket.bucket_time.format("%m-%d %H:00").to_string(),
-                MetricsGranularity::Daily => bucket.bucket_time.format("%Y-%m-%d").to_string(),
-            };
+    let rows: Vec<Row> = app
+        .metrics_buckets()
+        .into_iter()
+        .map(|bucket| {
+            let time_format = format_bucket_time(app.metrics_granularity, &bucket.bucket_time);
 
             let success_rate = bucket
                 .success_rate_percent
                 .map(|r| format!("{:.1}%", r))
                 .unwrap_or_else(|| "-".to_string());
 
-            let success_rate_color = bucket.success_rate_percent.map_or(Color::DarkGray, |r| {
-                if r >= 95.0 {
-                    Color::Green
-                } else if r >= 80.0 {
-                    Color::Yellow
-                } else {
-                    Color::Red
-                }
-            });
+            let success_rate_color = success_rate_color(bucket.success_rate_percent);
 
             let avg_duration = bucket
                 .avg_duration_seconds
@@ -381,17 +672,25 @@ fn draw_metrics(f: &mut Frame, app: &App, area: Rect) {
                 Cell::from(avg_duration),
                 Cell::from(avg_memory),
             ])
-            .style(style)
         })
         .collect();
 
+    let server = if app.multi_server() {
+        format!(" @ {}", app.metrics_server().name)
+    } else {
+        String::new()
+    };
     let title = format!(
-        " Metrics ({} - {}) ({} buckets) ",
+        " Metrics: {} ({} - {}) ({} buckets){}{} ",
+        app.metrics_range.label(),
         metrics.start_time.format("%m-%d %H:%M"),
         metrics.end_time.format("%m-%d %H:%M"),
-        metrics.buckets.len()
+        metrics.buckets.len(),
+        server,
+        state_suffix(app.metrics_server().metrics_state)
     );
 
+    let len = rows.len();
     let table = Table::new(
         rows,
         [
@@ -407,93 +706,509 @@ fn draw_metrics(f: &mut Frame, app: &App, area: Rect) {
     .header(header)
     .block(Block::default().borders(Borders::ALL).title(title));
 
-    f.render_widget(table, chunks[1]);
+    draw_table(
+        f,
+        table,
+        table_area,
+        &app.metrics_view,
+        app.metrics_selected,
+        len,
+    );
 }
 
-fn draw_health(f: &mut Frame, app: &App, area: Rect) {
-    let content = match &app.health {
-        Some(health) => {
-            let healthy_style = if health.healthy {
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
-            };
+/// Height of the metrics chart pane
+const METRICS_CHART_HEIGHT: u16 = 12;
 
-            let lines = vec![
-                Line::from(vec![
-                    Span::raw("  Status:           "),
-                    Span::styled(
-                        if health.healthy {
-                            "Healthy"
-                        } else {
-                            "Unhealthy"
-                        },
-                        healthy_style,
-                    ),
-                ]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::raw("  Version:          "),
-                    Span::styled(&health.version, Style::default().fg(Color::Cyan)),
-                ]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::raw("  Uptime:           "),
-                    Span::styled(
-                        format_duration(health.uptime_ms as u64),
-                        Style::default().fg(Color::Cyan),
-                    ),
-                ]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::raw("  Active Instances: "),
-                    Span::styled(
-                        health.active_instances.to_string(),
-                        Style::default().fg(Color::Cyan),
-                    ),
-                ]),
-                Line::from(""),
-                Line::from(""),
-                Line::from(vec![
-                    Span::raw("  Server:           "),
-                    Span::styled(
-                        app.server_addr.to_string(),
-                        Style::default().fg(Color::White),
-                    ),
-                ]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::raw("  Last Refresh:     "),
-                    Span::styled(
-                        app.last_refresh
-                            .map(|t| format!("{}s ago", t.elapsed().as_secs()))
-                            .unwrap_or_else(|| "Never".to_string()),
-                        Style::default().fg(Color::White),
-                    ),
-                ]),
-            ];
+/// Metrics area height below which the charts are left out for the table
+const METRICS_CHARTS_MIN_HEIGHT: u16 = 24;
 
-            Text::from(lines)
+/// Width of the invocation bars in the tenant comparison
+const COMPARISON_BAR_WIDTH: u16 = 20;
+
+/// Invocation, duration and memory charts of the buckets in time order,
+/// with the bucket selected in the table marked
+fn draw_metrics_charts(f: &mut Frame, app: &App, metrics: &TenantMetricsResult, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(40),
+            Constraint::Percentage(35),
+            Constraint::Percentage(25),
+        ])
+        .split(area);
+
+    let mut buckets: Vec<&MetricsBucket> = metrics.buckets.iter().collect();
+    buckets.sort_by_key(|bucket| bucket.bucket_time);
+    let selected = app
+        .metrics_buckets()
+        .get(app.metrics_selected)
+        .and_then(|s| buckets.iter().position(|b| b.bucket_time == s.bucket_time));
+    let selected_bucket = selected.map(|i| buckets[i]);
+
+    let x_axis = || {
+        let label = |bucket: Option<&&MetricsBucket>| {
+            bucket
+                .map(|b| format_bucket_time(app.metrics_granularity, &b.bucket_time))
+                .unwrap_or_default()
+        };
+        Axis::default()
+            .style(Style::default().fg(Color::DarkGray))
+            .bounds([-0.5, buckets.len() as f64 - 0.5])
+            .labels([label(buckets.first()), label(buckets.last())])
+    };
+    let points = |value: &dyn Fn(&MetricsBucket) -> Option<f64>| -> Vec<(f64, f64)> {
+        buckets
+            .iter()
+            .enumerate()
+            .filter_map(|(i, bucket)| value(bucket).map(|v| (i as f64, v)))
+            .collect()
+    };
+    // Vertical line through the selected bucket; bars drawn later cover its
+    // lower part
+    let cursor = |top: f64| -> Vec<(f64, f64)> {
+        selected
+            .map(|i| vec![(i as f64, 0.0), (i as f64, top)])
+            .unwrap_or_default()
+    };
+    // Title with a colour key; the values of the selected bucket go on the
+    // bottom border
+    let chart_block = |title: &str, keys: &[(&str, Color)], values: Option<String>| {
+        let mut spans = vec![Span::raw(format!(" {} ", title))];
+        for (label, color) in keys {
+            spans.push(Span::styled(
+                format!("■ {} ", label),
+                Style::default().fg(*color),
+            ));
         }
-        None => Text::from(vec![
-            Line::from(""),
-            Line::from(Span::styled(
-                "  No health data available",
-                Style::default().fg(Color::Yellow),
-            )),
-            Line::from(""),
-            Line::from("  Press 'r' to refresh"),
-        ]),
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .title(Line::from(spans))
+            .border_style(Style::default().fg(Color::DarkGray));
+        if let (Some(values), Some(bucket)) = (values, selected_bucket) {
+            block = block.title_bottom(
+                Line::from(format!(
+                    " {}: {} ",
+                    format_bucket_time(app.metrics_granularity, &bucket.bucket_time),
+                    values
+                ))
+                .right_aligned(),
+            );
+        }
+        block
     };
 
-    let paragraph = Paragraph::new(content)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" Health Status "),
+    // Invocations: successes in green stacked under the rest in red
+    let totals = points(&|b| Some(b.invocation_count as f64));
+    let successes = points(&|b| Some(b.success_count as f64));
+    let max_count = totals.iter().map(|p| p.1).fold(1.0, f64::max);
+    let count_cursor = cursor(max_count);
+    let values = selected_bucket.map(|b| {
+        format!(
+            "{} ok, {} failed, {} cancelled",
+            b.success_count, b.failure_count, b.cancelled_count
         )
+    });
+    let chart = Chart::new(vec![
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Yellow))
+            .data(&count_cursor),
+        Dataset::default()
+            .marker(Marker::HalfBlock)
+            .graph_type(GraphType::Bar)
+            .style(Style::default().fg(Color::Red))
+            .data(&totals),
+        Dataset::default()
+            .marker(Marker::HalfBlock)
+            .graph_type(GraphType::Bar)
+            .style(Style::default().fg(Color::Green))
+            .data(&successes),
+    ])
+    .block(chart_block(
+        "Invocations",
+        &[("ok", Color::Green), ("failed/cancelled", Color::Red)],
+        values,
+    ))
+    .x_axis(x_axis())
+    .y_axis(
+        Axis::default()
+            .style(Style::default().fg(Color::DarkGray))
+            .bounds([0.0, max_count])
+            .labels(["0".to_string(), format!("{}", max_count)]),
+    );
+    f.render_widget(chart, chunks[0]);
+
+    // Average and maximum duration
+    let averages = points(&|b| b.avg_duration_seconds);
+    let maximums = points(&|b| b.max_duration_seconds);
+    let max_seconds = maximums
+        .iter()
+        .chain(&averages)
+        .map(|p| p.1)
+        .fold(0.001, f64::max);
+    let duration_cursor = cursor(max_seconds);
+    let seconds = |s: f64| format_duration((s * 1000.0) as u64);
+    let values = selected_bucket
+        .and_then(|b| b.avg_duration_seconds.zip(b.max_duration_seconds))
+        .map(|(avg, max)| format!("avg {}, max {}", seconds(avg), seconds(max)));
+    let chart = Chart::new(vec![
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Yellow))
+            .data(&duration_cursor),
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Magenta))
+            .data(&maximums),
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Cyan))
+            .data(&averages),
+    ])
+    .block(chart_block(
+        "Duration",
+        &[("avg", Color::Cyan), ("max", Color::Magenta)],
+        values,
+    ))
+    .x_axis(x_axis())
+    .y_axis(
+        Axis::default()
+            .style(Style::default().fg(Color::DarkGray))
+            .bounds([0.0, max_seconds])
+            .labels(["0".to_string(), seconds(max_seconds)]),
+    );
+    f.render_widget(chart, chunks[1]);
+
+    // Average memory, one column per bucket; the newest that fit are shown
+    let values = selected_bucket
+        .and_then(|b| b.avg_memory_bytes)
+        .map(|bytes| format_bytes(bytes.max(0) as u64));
+    let block = chart_block("Avg Memory", &[], values);
+    let width = block.inner(chunks[2]).width as usize;
+    let skip = buckets.len().saturating_sub(width);
+    let bars: Vec<SparklineBar> = buckets
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .map(|(i, bucket)| {
+            let bar = SparklineBar::from(bucket.avg_memory_bytes.map(|m| m.max(0) as u64));
+            if Some(i) == selected {
+                bar.style(Some(Style::default().fg(Color::Yellow)))
+            } else {
+                bar
+            }
+        })
+        .collect();
+    let sparkline = Sparkline::default()
+        .block(block)
+        .style(Style::default().fg(Color::Blue))
+        .data(bars);
+    f.render_widget(sparkline, chunks[2]);
+}
+
+/// Metrics of the compared tenants, one row each
+fn draw_metrics_comparison(f: &mut Frame, app: &App, area: Rect) {
+    let server = app.metrics_server();
+    let header = Row::new(
+        [
+            "Tenant",
+            "Invocations",
+            "",
+            "Success",
+            "Failed",
+            "Cancelled",
+            "Success %",
+            "Avg Duration",
+            "Max Duration",
+        ]
+        .map(|title| Cell::from(title).style(Style::default().add_modifier(Modifier::BOLD))),
+    )
+    .height(1)
+    .style(Style::default().fg(Color::Yellow));
+
+    let totals: Vec<_> = server
+        .comparison
+        .iter()
+        .map(|(tenant, result)| (tenant, result.as_ref().map(TenantTotals::new)))
+        .collect();
+    let most = totals
+        .iter()
+        .filter_map(|(_, totals)| totals.as_ref().ok())
+        .map(|totals| totals.invocations)
+        .max()
+        .unwrap_or(0);
+    let seconds = |s: Option<f64>| s.map_or("-".to_string(), |s| format!("{:.2}s", s));
+
+    let mut rows: Vec<Row> = totals
+        .iter()
+        .map(|(tenant, totals)| {
+            let totals = match totals {
+                Ok(totals) => totals,
+                // The error goes below the tenant, where there is room for it
+                Err(e) => {
+                    return Row::new(vec![Cell::from(Text::from(vec![
+                        Line::from(tenant.to_string()),
+                        Line::styled(format!("  {}", e), Style::default().fg(Color::Red)),
+                    ]))])
+                    .height(2)
+                }
+            };
+            let bar_width = if most > 0 {
+                (totals.invocations * COMPARISON_BAR_WIDTH as i64 / most) as usize
+            } else {
+                0
+            };
+            let success_rate = totals.success_rate();
+            Row::new(vec![
+                Cell::from(tenant.to_string()),
+                Cell::from(totals.invocations.to_string()),
+                Cell::from("█".repeat(bar_width)).style(Style::default().fg(Color::Cyan)),
+                Cell::from(totals.success.to_string()).style(Style::default().fg(Color::Green)),
+                Cell::from(totals.failed.to_string()).style(Style::default().fg(
+                    if totals.failed > 0 {
+                        Color::Red
+                    } else {
+                        Color::DarkGray
+                    },
+                )),
+                Cell::from(totals.cancelled.to_string()),
+                Cell::from(success_rate.map_or("-".to_string(), |r| format!("{:.1}%", r)))
+                    .style(Style::default().fg(success_rate_color(success_rate))),
+                Cell::from(seconds(totals.avg_duration_seconds)),
+                Cell::from(seconds(totals.max_duration_seconds)),
+            ])
+        })
+        .collect();
+    if rows.is_empty() && server.comparison_state == LoadState::Loading {
+        rows.push(Row::new(vec![
+            Cell::from("Loading metrics…").style(Style::default().fg(Color::Yellow))
+        ]));
+    }
+
+    let server_name = if app.multi_server() {
+        format!(" @ {}", server.name)
+    } else {
+        String::new()
+    };
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(20),
+            Constraint::Length(12),
+            Constraint::Length(COMPARISON_BAR_WIDTH + 1),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(13),
+            Constraint::Length(13),
+        ],
+    )
+    .header(header)
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        " Tenant Comparison: {} ({} tenants){}{} ",
+        app.metrics_range.label(),
+        app.compare_tenants.len(),
+        server_name,
+        state_suffix(server.comparison_state)
+    )));
+
+    f.render_widget(table, area);
+}
+
+/// Color of a success rate
+fn success_rate_color(rate: Option<f64>) -> Color {
+    rate.map_or(Color::DarkGray, |r| {
+        if r >= 95.0 {
+            Color::Green
+        } else if r >= 80.0 {
+            Color::Yellow
+        } else {
+            Color::Red
+        }
+    })
+}
+
+/// Bucket start time at the granularity of the bucket
+fn format_bucket_time(granularity: MetricsGranularity, time: &DateTime<Utc>) -> String {
+    match granularity {
+        MetricsGranularity::Hourly => time.format("%m-%d %H:00").to_string(),
+        MetricsGranularity::Daily => time.format("%Y-%m-%d").to_string(),
+    }
+}
+
+fn draw_health(f: &mut Frame, app: &App, area: Rect) {
+    match app.focus {
+        None if app.multi_server() => draw_health_summary(f, app, area),
+        focus => draw_server_health(f, app, &app.servers[focus.unwrap_or(0)], area),
+    }
+}
+
+/// One row per server: connection, health and the last error
+fn draw_health_summary(f: &mut Frame, app: &App, area: Rect) {
+    let header = Row::new(vec![
+        Cell::from("Server").style(Style::default().add_modifier(Modifier::BOLD)),
+        Cell::from("Address").style(Style::default().add_modifier(Modifier::BOLD)),
+        Cell::from("Connection").style(Style::default().add_modifier(Modifier::BOLD)),
+        Cell::from("Status").style(Style::default().add_modifier(Modifier::BOLD)),
+        Cell::from("Version").style(Style::default().add_modifier(Modifier::BOLD)),
+        Cell::from("Uptime").style(Style::default().add_modifier(Modifier::BOLD)),
+        Cell::from("Active").style(Style::default().add_modifier(Modifier::BOLD)),
+        Cell::from("Last Error").style(Style::default().add_modifier(Modifier::BOLD)),
+    ])
+    .height(1)
+    .style(Style::default().fg(Color::Yellow));
+
+    let rows: Vec<Row> = app
+        .servers
+        .iter()
+        .map(|server| {
+            let (status, status_color) = match (&server.health, server.health_state) {
+                (_, LoadState::Stale) => ("Stale", Color::Red),
+                (Some(h), _) if h.healthy => ("Healthy", Color::Green),
+                (Some(_), _) => ("Unhealthy", Color::Red),
+                (None, _) => ("-", Color::DarkGray),
+            };
+            let health = server.health.as_ref();
+            Row::new(vec![
+                Cell::from(truncate(&server.name, SERVER_WIDTH as usize))
+                    .style(Style::default().fg(Color::Cyan)),
+                Cell::from(truncate(&server.address, 28)),
+                Cell::from(Line::from(connection_span(&server.connection))),
+                Cell::from(status).style(Style::default().fg(status_color)),
+                Cell::from(health.map_or("-".to_string(), |h| h.version.clone())),
+                Cell::from(health.map_or("-".to_string(), |h| format_duration(h.uptime_ms as u64))),
+                Cell::from(health.map_or("-".to_string(), |h| h.active_instances.to_string())),
+                Cell::from(server.error.clone().unwrap_or_default())
+                    .style(Style::default().fg(Color::Red)),
+            ])
+        })
+        .collect();
+
+    let state = LoadState::combine(app.servers.iter().map(|s| s.health_state));
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(SERVER_WIDTH + 2),
+            Constraint::Length(30),
+            Constraint::Length(16),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Min(20),
+        ],
+    )
+    .header(header)
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        " Health ({} servers){} ",
+        app.servers.len(),
+        state_suffix(state)
+    )));
+
+    f.render_widget(table, area);
+}
+
+fn draw_server_health(f: &mut Frame, app: &App, server: &Server, area: Rect) {
+    let content = match &server.health {
+        Some(health) => {
+            let healthy_style = if health.healthy {
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+            };
+
+            let lines = vec![
+                Line::from(vec![
+                    Span::raw("  Status:           "),
+                    Span::styled(
+                        if health.healthy {
+                            "Healthy"
+                        } else {
+                            "Unhealthy"
+                        },
+                        healthy_style,
+                    ),
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::raw("  Version:          "),
+                    Span::styled(&health.version, Style::default().fg(Color::Cyan)),
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::raw("  Uptime:           "),
+                    Span::styled(
+                        format_duration(health.uptime_ms as u64),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::raw("  Active Instances: "),
+                    Span::styled(
+                        health.active_instances.to_string(),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                ]),
+                Line::from(""),
+                Line::from(""),
+                Line::from(vec![
+                    Span::raw("  Server:           "),
+                    Span::styled(&server.address, Style::default().fg(Color::White)),
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::raw("  Address:          "),
+                    Span::styled(
+                        match server.connection {
+                            ConnectionState::Connected { addr } => addr.to_string(),
+                            _ => "-".to_string(),
+                        },
+                        Style::default().fg(Color::White),
+                    ),
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::raw("  Last Refresh:     "),
+                    Span::styled(
+                        app.last_refresh
+                            .map(|t| format!("{}s ago", t.elapsed().as_secs()))
+                            .unwrap_or_else(|| "Never".to_string()),
+                        Style::default().fg(Color::White),
+                    ),
+                ]),
+            ];
+
+            Text::from(lines)
+        }
+        None => Text::from(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                if server.health_state == LoadState::Loading {
+                    "  Loading health status…"
+                } else {
+                    "  No health data available"
+                },
+                Style::default().fg(Color::Yellow),
+            )),
+            Line::from(""),
+            Line::from("  Press 'r' to refresh"),
+        ]),
+    };
+
+    let paragraph = Paragraph::new(content)
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            " Health Status{}{} ",
+            focus_suffix(app),
+            state_suffix(server.health_state)
+        )))
         .wrap(Wrap { trim: false });
 
     f.render_widget(paragraph, area);
@@ -501,32 +1216,67 @@ fn draw_health(f: &mut Frame, app: &App, area: Rect) {
 
 fn draw_footer(f: &mut Frame, app: &App, area: Rect) {
     let help_text = match app.view_mode {
+        _ if app
+            .prompt
+            .as_ref()
+            .is_some_and(|prompt| prompt.kind == PromptKind::Query) =>
+        {
+            "Enter:Apply | Esc:Cancel | ↑/↓:History | Ctrl-U:Clear | $.a.b[*]  $..id  $.a[?@.n > 1]  .a[].b"
+        }
+        _ if app.prompt.as_ref().is_some_and(|prompt| {
+            matches!(prompt.kind, PromptKind::Export | PromptKind::ExportBundle)
+        }) =>
+        {
+            "Enter:Export | Esc:Cancel | Ctrl-U:Clear | Existing files are overwritten"
+        }
+        _ if app
+            .prompt
+            .as_ref()
+            .is_some_and(|prompt| prompt.kind == PromptKind::MetricsRange) =>
+        {
+            "Enter:Apply | Esc:Cancel | Ctrl-U:Clear | 12h  2025-01-31..2025-02-01  2025-01-31 08:00..now  7d..1d"
+        }
+        _ if app
+            .prompt
+            .as_ref()
+            .is_some_and(|prompt| prompt.kind == PromptKind::Tenant) =>
+        {
+            "Enter:Select | Esc:Cancel | ↑/↓:Move | Ctrl-U:Clear | Type to filter tenants or enter a new one"
+        }
+        ViewMode::List if app.prompt.is_some() => {
+            "Enter:Apply | Esc:Cancel | Ctrl-U:Clear | created>7d created<2025-01-31 finished>6h image:PREFIX"
+        }
         ViewMode::List => match app.tab {
             Tab::Instances => {
-                "q:Quit | Tab:Switch Tab | 1-4:Tab | j/k:Navigate | Enter:Details | f:Filter | r:Refresh"
+                "q:Quit | Tab:Switch Tab | 1-4:Tab | j/k:Navigate | PgUp/PgDn/gg/G:Scroll | [/]:Page | Enter:Details | o/O:Sort/Reverse | y:Copy ID | f:Filter | /:Search | F:Filter bar | x:Cancel | p:Suspend | u:Resume | R:Re-run | P:Profile | T:Tenant | r:Refresh"
             }
-            Tab::Images => "q:Quit | Tab:Switch Tab | 1-4:Tab | j/k:Navigate | r:Refresh",
-            Tab::Metrics => "q:Quit | Tab:Switch Tab | 1-4:Tab | j/k:Navigate | g:Granularity | r:Refresh",
-            Tab::Health => "q:Quit | Tab:Switch Tab | 1-4:Tab | r:Refresh",
+            Tab::Images => "q:Quit | Tab:Switch Tab | 1-4:Tab | j/k:Navigate | PgUp/PgDn/gg/G:Scroll | [/]:Page | Enter:Metrics | o/O:Sort/Reverse | y:Copy ID | P:Profile | T:Tenant | r:Refresh",
+            Tab::Metrics => "q:Quit | Tab:Switch Tab | 1-4:Tab | j/k:Navigate | PgUp/PgDn/gg/G:Scroll | o/O:Sort/Reverse | h:Granularity | t:Range | m:Tenants | P:Profile | T:Tenant | r:Refresh",
+            Tab::Health => "q:Quit | Tab:Switch Tab | 1-4:Tab | P:Profile | T:Tenant | r:Refresh",
         },
         ViewMode::InstanceDetail => {
-            "Esc:Back | c:Checkpoints | j/k:Scroll"
+            "Esc:Back | c:Checkpoints | t:Timeline | f:Follow | /:Query | y/Y:Copy ID/JSON | e:Export | E:Export bundle | j/k:Move | h/l:Fold | Enter:Toggle | J/K:Sibling | H:Parent | +/-:Expand/Collapse all | x:Cancel | p:Suspend | u:Resume | R:Re-run"
         }
         ViewMode::CheckpointsList => {
-            "Esc:Back | Enter:View Data | j/k:Navigate"
+            "Esc:Back | Enter:View Data | j/k:Navigate | PgUp/PgDn/gg/G:Scroll | [/]:Page | o/O:Sort/Reverse | y:Copy ID | Space:Mark | d:Diff marked"
+        }
+        ViewMode::Timeline => {
+            "Esc:Back | j/k:Move | PgUp/PgDn:Page | gg/G:First/Last | s:Slowest step | y:Copy ID"
+        }
+        ViewMode::CheckpointDiff => {
+            "Esc:Back | j/k:Move | PgUp/PgDn:Page | gg/G:First/Last | y:Copy path | v:Unified/Side by side"
+        }
+        ViewMode::ImageMetrics => {
+            "Esc:Back | j/k:Navigate | PgUp/PgDn/gg/G:Scroll | h:Granularity | t:Range | y:Copy ID | r:Refresh"
         }
         ViewMode::CheckpointDetail => {
-            "Esc:Back | j/k:Scroll"
+            "Esc:Back | /:Query | y/Y:Copy ID/JSON | e:Export | j/k:Move | h/l:Fold | Enter:Toggle | J/K:Sibling | H:Parent | +/-:Expand/Collapse all | PgUp/PgDn:Page"
         }
     };
 
-    let tenant_info = app
-        .tenant_id
-        .as_ref()
-        .map(|t| format!(" | Tenant: {}", t))
-        .unwrap_or_default();
-
-    let refresh_info = if app.view_mode == ViewMode::List {
+    let refresh_info = if app.refreshing() {
+        " | Refreshing…".to_string()
+    } else if app.view_mode == ViewMode::List {
         app.last_refresh
             .map(|t| {
                 format!(
@@ -541,12 +1291,32 @@ fn draw_footer(f: &mut Frame, app: &App, area: Rect) {
         String::new()
     };
 
+    let mut block = Block::default().borders(Borders::ALL);
+    if let Some(flash) = app.active_flash() {
+        let color = if flash.is_error {
+            Color::Red
+        } else {
+            Color::Green
+        };
+        block = block.title(Span::styled(
+            format!(" {} ", flash.text),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    // Server focus is only offered with several servers
+    let focus_help = if app.multi_server() && app.view_mode == ViewMode::List {
+        " | s:Server"
+    } else {
+        ""
+    };
+
     let footer = Paragraph::new(Line::from(vec![
         Span::styled(help_text, Style::default().fg(Color::DarkGray)),
-        Span::styled(tenant_info, Style::default().fg(Color::Cyan)),
+        Span::styled(focus_help, Style::default().fg(Color::DarkGray)),
         Span::styled(refresh_info, Style::default().fg(Color::DarkGray)),
     ]))
-    .block(Block::default().borders(Borders::ALL));
+    .block(block);
 
     f.render_widget(footer, area);
 }
@@ -557,7 +1327,10 @@ fn draw_instance_detail_modal(f: &mut Frame, app: &App) {
 
     let info = match &app.instance_detail {
         Some(info) => info,
-        None => return,
+        None => {
+            draw_loading_modal(f, area, " Instance Details ", Color::Cyan);
+            return;
+        }
     };
 
     let (status_text, status_color) = status_style(info.status);
@@ -627,6 +1400,7 @@ fn draw_instance_detail_modal(f: &mut Frame, app: &App) {
                     .unwrap_or_else(|| "-".to_string()),
                 Style::default().fg(Color::White),
             ),
+            heartbeat_age_span(app, info),
         ]),
         Line::from(""),
         Line::from(vec![
@@ -645,49 +1419,6 @@ fn draw_instance_detail_modal(f: &mut Frame, app: &App) {
         ]),
     ];
 
-    // Add input if present
-    if let Some(input) = &info.input {
-        lines.push(Line::from(""));
-        lines.push(Line::from(Span::styled(
-            "  Input:",
-            Style::default().fg(Color::DarkGray),
-        )));
-        let input_str =
-            serde_json::to_string_pretty(input).unwrap_or_else(|_| format!("{:?}", input));
-        for line in input_str.lines().take(5) {
-            lines.push(Line::from(format!("    {}", line)));
-        }
-        if input_str.lines().count() > 5 {
-            lines.push(Line::from(Span::styled(
-                "    ...",
-                Style::default().fg(Color::DarkGray),
-            )));
-        }
-    }
-
-    // Add output if present
-    if let Some(output) = &info.output {
-        lines.push(Line::from(""));
-        lines.push(Line::from(Span::styled(
-            "  Output:",
-            Style::default().fg(Color::DarkGray),
-        )));
-        let output_str =
-            serde_json::to_string_pretty(output).unwrap_or_else(|_| format!("{:?}", output));
-        for line in output_str.lines().take(5) {
-            lines.push(Line::from(Span::styled(
-                format!("    {}", line),
-                Style::default().fg(Color::Green),
-            )));
-        }
-        if output_str.lines().count() > 5 {
-            lines.push(Line::from(Span::styled(
-                "    ...",
-                Style::default().fg(Color::DarkGray),
-            )));
-        }
-    }
-
     // Add error if present
     if let Some(error) = &info.error {
         lines.push(Line::from(""));
@@ -703,62 +1434,192 @@ fn draw_instance_detail_modal(f: &mut Frame, app: &App) {
         }
     }
 
-    let paragraph = Paragraph::new(Text::from(lines))
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
-                .title(" Instance Details "),
-        )
-        .scroll((app.detail_scroll, 0))
-        .wrap(Wrap { trim: false });
-
-    f.render_widget(paragraph, area);
-}
-
-fn draw_checkpoints_list_modal(f: &mut Frame, app: &App) {
-    let area = centered_rect(80, 70, f.area());
-    f.render_widget(Clear, area);
+    lines.push(Line::from(""));
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(if app.multi_server() {
+            format!(
+                " Instance Details @ {} ",
+                app.servers[app.detail_server].name
+            )
+        } else {
+            " Instance Details ".to_string()
+        });
+    if let Some(follow) = &app.follow {
+        let (label, color) = if follow.finished {
+            (" ■ Finished ", Color::DarkGray)
+        } else {
+            (" ● Following ", Color::Green)
+        };
+        block = block.title(
+            Line::from(Span::styled(
+                label,
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ))
+            .right_aligned(),
+        );
+    }
+    let inner = block.inner(area);
+    f.render_widget(block, area);
 
-    let instance_id = app
-        .instance_detail
+    // Recent events of a followed instance, plus the rule above them
+    let live_height = app
+        .follow
         .as_ref()
-        .map(|i| i.instance_id.as_str())
-        .unwrap_or("Unknown");
+        .map_or(0, |follow| follow.log.len().clamp(1, FOLLOW_LOG_LINES) + 1);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(lines.len() as u16),
+            Constraint::Length(live_height as u16),
+            Constraint::Min(3),
+        ])
+        .split(inner);
+    f.render_widget(
+        Paragraph::new(Text::from(lines)).wrap(Wrap { trim: false }),
+        chunks[0],
+    );
 
-    let header = Row::new(vec![
-        Cell::from("Checkpoint ID").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Created At").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Size").style(Style::default().add_modifier(Modifier::BOLD)),
-    ])
-    .height(1)
-    .style(Style::default().fg(Color::Yellow));
+    if let Some(follow) = &app.follow {
+        draw_follow_log(f, follow, chunks[1]);
+    }
 
-    let rows: Vec<Row> = app
-        .checkpoints
-        .iter()
-        .enumerate()
-        .map(|(i, cp)| {
-            let is_selected = i == app.checkpoints_selected;
+    if let Some(tree) = &app.instance_tree {
+        draw_json_tree(f, app, tree, " Input / Output ", chunks[2]);
+    }
+}
 
-            let style = if is_selected {
-                Style::default().bg(Color::DarkGray)
-            } else {
-                Style::default()
-            };
+/// Events shown while following an instance
+const FOLLOW_LOG_LINES: usize = 5;
+
+/// Latest status changes and checkpoints of a followed instance
+fn draw_follow_log(f: &mut Frame, follow: &Follow, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(Span::styled(
+            format!(" Live ({} events) ", follow.log.len()),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    if follow.log.is_empty() {
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                "  Waiting for status changes and new checkpoints…",
+                Style::default().fg(Color::DarkGray),
+            )),
+            inner,
+        );
+        return;
+    }
+
+    let skip = follow.log.len().saturating_sub(FOLLOW_LOG_LINES);
+    let lines: Vec<Line> = follow.log[skip..]
+        .iter()
+        .map(|(at, event)| {
+            let mut spans = vec![Span::styled(
+                format!("  {}  ", at.format("%H:%M:%S")),
+                Style::default().fg(Color::DarkGray),
+            )];
+            match event {
+                FollowEvent::Status { from, to } => {
+                    let (from_text, from_color) = status_style(*from);
+                    let (to_text, to_color) = status_style(*to);
+                    spans.push(Span::styled(from_text, Style::default().fg(from_color)));
+                    spans.push(Span::raw(" → "));
+                    spans.push(Span::styled(
+                        to_text,
+                        Style::default().fg(to_color).add_modifier(Modifier::BOLD),
+                    ));
+                }
+                FollowEvent::Checkpoint { id, size } => {
+                    spans.push(Span::styled(
+                        format!("+ checkpoint {}", id),
+                        Style::default().fg(Color::Cyan),
+                    ));
+                    spans.push(Span::styled(
+                        format!(" ({})", format_bytes(*size)),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+            }
+            Line::from(spans)
+        })
+        .collect();
+    f.render_widget(Paragraph::new(Text::from(lines)), inner);
+}
+
+/// Age of the last heartbeat of an unfinished instance, red once stale
+fn heartbeat_age_span(app: &App, info: &InstanceInfo) -> Span<'static> {
+    let Some(at) = info.heartbeat_at.filter(|_| !info.status.is_terminal()) else {
+        return Span::raw("");
+    };
+    let age = (Utc::now() - at).to_std().unwrap_or_default();
+    let style = if info.status == InstanceStatus::Running && app.is_stale(at) {
+        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    Span::styled(
+        format!("  ({} ago)", format_duration(age.as_millis() as u64)),
+        style,
+    )
+}
+
+fn draw_checkpoints_list_modal(f: &mut Frame, app: &App) {
+    let area = centered_rect(80, 70, f.area());
+    f.render_widget(Clear, area);
+
+    let instance_id = app
+        .instance_detail
+        .as_ref()
+        .map(|i| i.instance_id.as_str())
+        .unwrap_or("Unknown");
+
+    let header = Row::new(vec![
+        Cell::from(""),
+        Cell::from("Checkpoint ID").style(Style::default().add_modifier(Modifier::BOLD)),
+        sort_header(&app.checkpoint_sort, CheckpointColumn::Time),
+        sort_header(&app.checkpoint_sort, CheckpointColumn::Size),
+    ])
+    .height(1)
+    .style(Style::default().fg(Color::Yellow));
+
+    let rows: Vec<Row> = app
+        .visible_checkpoints()
+        .into_iter()
+        .map(|cp| {
+            let is_marked = app
+                .checkpoints_marked
+                .iter()
+                .any(|marked| marked.checkpoint_id == cp.checkpoint_id);
 
             Row::new(vec![
+                Cell::from(if is_marked { "●" } else { "" })
+                    .style(Style::default().fg(Color::Magenta)),
                 Cell::from(truncate(&cp.checkpoint_id, 40)),
                 Cell::from(format_datetime(&cp.created_at)),
                 Cell::from(format_bytes(cp.data_size_bytes)),
             ])
-            .style(style)
         })
         .collect();
 
+    let marked = match app.checkpoints_marked.len() {
+        0 => String::new(),
+        n => format!(", {} marked", n),
+    };
+
+    let len = rows.len();
     let table = Table::new(
         rows,
         [
+            Constraint::Length(1),
             Constraint::Min(40),
             Constraint::Length(20),
             Constraint::Length(12),
@@ -770,13 +1631,210 @@ fn draw_checkpoints_list_modal(f: &mut Frame, app: &App) {
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Cyan))
             .title(format!(
-                " Checkpoints for {} ({}) ",
+                " Checkpoints for {} ({}{}){} ",
                 truncate(instance_id, 20),
-                app.checkpoints_total
+                rows_label(
+                    app.checkpoints_offset,
+                    app.checkpoints.len(),
+                    app.checkpoints_total
+                ),
+                marked,
+                state_suffix(app.checkpoints_state)
             )),
     );
 
-    f.render_widget(table, area);
+    draw_table(
+        f,
+        table,
+        area,
+        &app.checkpoints_view,
+        app.checkpoints_selected,
+        len,
+    );
+}
+
+fn draw_image_metrics_modal(f: &mut Frame, app: &App) {
+    let area = centered_rect(85, 85, f.area());
+    f.render_widget(Clear, area);
+
+    let Some(image) = &app.metrics_image else {
+        return;
+    };
+    let title = format!(" Image Metrics: {} ", truncate(&image.name, 40));
+    let metrics = match &app.image_metrics {
+        Some(metrics) => metrics,
+        None => {
+            draw_loading_modal(f, area, &title, Color::Magenta);
+            return;
+        }
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(7), Constraint::Min(0)])
+        .split(area);
+
+    let label = Style::default().fg(Color::DarkGray);
+    let value = Style::default().fg(Color::White);
+    let run_time =
+        |duration: Option<chrono::Duration>| duration.map_or("-".to_string(), format_gap);
+    let stats = &metrics.total;
+    let counted = if metrics.counted() < metrics.matched as usize {
+        format!(" (newest {} of {})", metrics.counted(), metrics.matched)
+    } else {
+        String::new()
+    };
+    let summary = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled("  Image: ", label),
+            Span::styled(image.image_id.clone(), value),
+            Span::styled("   Runner: ", label),
+            Span::styled(format!("{:?}", image.runner_type), value),
+            Span::styled("   Tenant: ", label),
+            Span::styled(image.tenant_id.clone(), value),
+        ]),
+        Line::from(vec![
+            Span::styled("  Range: ", label),
+            Span::styled(
+                format!(
+                    "{} – {}",
+                    metrics.start.format("%Y-%m-%d %H:%M"),
+                    metrics.end.format("%Y-%m-%d %H:%M")
+                ),
+                value,
+            ),
+            Span::styled(format!(" ({})", app.metrics_range.label()), label),
+        ]),
+        Line::from(vec![
+            Span::styled("  Runs: ", label),
+            Span::styled(format!("{}{}", stats.invocations, counted), value),
+            Span::styled("   Completed: ", label),
+            Span::styled(
+                stats.completed.to_string(),
+                Style::default().fg(Color::Green),
+            ),
+            Span::styled("   Failed: ", label),
+            Span::styled(stats.failed.to_string(), Style::default().fg(Color::Red)),
+            Span::styled("   Cancelled: ", label),
+            Span::styled(stats.cancelled.to_string(), value),
+            Span::styled("   Unfinished: ", label),
+            Span::styled(stats.unfinished.to_string(), value),
+        ]),
+        Line::from(vec![
+            Span::styled("  Failure rate: ", label),
+            failure_rate_span(stats.failure_rate()),
+            Span::styled("   Run time p50: ", label),
+            Span::styled(run_time(stats.percentile(50.0)), value),
+            Span::styled("  p90: ", label),
+            Span::styled(run_time(stats.percentile(90.0)), value),
+            Span::styled("  p99: ", label),
+            Span::styled(run_time(stats.percentile(99.0)), value),
+            Span::styled("  max: ", label),
+            Span::styled(run_time(stats.max_duration()), value),
+        ]),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta))
+            .title(title)
+            .padding(Padding::top(1)),
+    );
+    f.render_widget(summary, chunks[0]);
+
+    let header = Row::new(
+        [
+            "Time",
+            "Runs",
+            "Completed",
+            "Failed",
+            "Failure %",
+            "p50",
+            "p90",
+            "p99",
+            "Max",
+        ]
+        .map(|title| Cell::from(title).style(Style::default().add_modifier(Modifier::BOLD))),
+    )
+    .height(1)
+    .style(Style::default().fg(Color::Yellow));
+
+    let rows: Vec<Row> = metrics
+        .buckets
+        .iter()
+        .map(|(time, stats)| {
+            Row::new(vec![
+                Cell::from(format_bucket_time(metrics.granularity, time)),
+                Cell::from(stats.invocations.to_string()),
+                Cell::from(stats.completed.to_string()).style(Style::default().fg(Color::Green)),
+                Cell::from(stats.failed.to_string()).style(Style::default().fg(
+                    if stats.failed > 0 {
+                        Color::Red
+                    } else {
+                        Color::DarkGray
+                    },
+                )),
+                Cell::from(failure_rate_span(stats.failure_rate())),
+                Cell::from(run_time(stats.percentile(50.0))),
+                Cell::from(run_time(stats.percentile(90.0))),
+                Cell::from(run_time(stats.percentile(99.0))),
+                Cell::from(run_time(stats.max_duration())),
+            ])
+        })
+        .collect();
+
+    let granularity = match metrics.granularity {
+        MetricsGranularity::Hourly => "Hourly",
+        MetricsGranularity::Daily => "Daily",
+    };
+    let len = rows.len();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(12),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(9),
+            Constraint::Length(9),
+            Constraint::Length(9),
+            Constraint::Min(9),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta))
+            .title(format!(" {} ({} buckets) ", granularity, len)),
+    );
+
+    draw_table(
+        f,
+        table,
+        chunks[1],
+        &app.image_metrics_view,
+        app.image_metrics_selected,
+        len,
+    );
+}
+
+/// Failure rate, colored like the success rate of the tenant metrics
+fn failure_rate_span(rate: Option<f64>) -> Span<'static> {
+    match rate {
+        Some(rate) => {
+            let color = if rate <= 5.0 {
+                Color::Green
+            } else if rate <= 20.0 {
+                Color::Yellow
+            } else {
+                Color::Red
+            };
+            Span::styled(format!("{:.1}%", rate), Style::default().fg(color))
+        }
+        None => Span::styled("-", Style::default().fg(Color::DarkGray)),
+    }
 }
 
 fn draw_checkpoint_detail_modal(f: &mut Frame, app: &App) {
@@ -785,10 +1843,13 @@ fn draw_checkpoint_detail_modal(f: &mut Frame, app: &App) {
 
     let checkpoint = match &app.checkpoint_detail {
         Some(cp) => cp,
-        None => return,
+        None => {
+            draw_loading_modal(f, area, " Checkpoint ", Color::Yellow);
+            return;
+        }
     };
 
-    let mut lines = vec![
+    let lines = vec![
         Line::from(""),
         Line::from(vec![
             Span::styled("  Checkpoint ID:  ", Style::default().fg(Color::DarkGray)),
@@ -809,40 +1870,871 @@ fn draw_checkpoint_detail_modal(f: &mut Frame, app: &App) {
             ),
         ]),
         Line::from(""),
-        Line::from(Span::styled(
-            "  Data:",
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(format!(
+            " Checkpoint: {} ",
+            truncate(&checkpoint.checkpoint_id, 30)
+        ));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(lines.len() as u16), Constraint::Min(3)])
+        .split(inner);
+    f.render_widget(Paragraph::new(Text::from(lines)), chunks[0]);
+
+    if let Some(tree) = &app.checkpoint_tree {
+        draw_json_tree(f, app, tree, " Data ", chunks[1]);
+    }
+}
+
+fn draw_checkpoint_diff_modal(f: &mut Frame, app: &App) {
+    let area = centered_rect(90, 85, f.area());
+    f.render_widget(Clear, area);
+
+    let diff = match &app.checkpoint_diff {
+        Some(diff) if diff.is_loaded() => diff,
+        _ => {
+            draw_loading_modal(f, area, " Checkpoint Diff ", Color::Magenta);
+            return;
+        }
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta))
+        .title(format!(
+            " Diff: {} → {} ",
+            truncate(&diff.old_id, 24),
+            truncate(&diff.new_id, 24)
+        ))
+        .title(
+            Line::from(if diff.side_by_side {
+                " side by side "
+            } else {
+                " unified "
+            })
+            .right_aligned(),
+        );
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let (added, removed, changed) = diff.counts();
+    let summary = vec![
+        Line::from(""),
+        checkpoint_line("  Old:  ", diff.old.as_ref(), Color::Red),
+        checkpoint_line("  New:  ", diff.new.as_ref(), Color::Green),
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                format!("+{} added", added),
+                Style::default().fg(Color::Green),
+            ),
+            Span::raw("  "),
+            Span::styled(
+                format!("-{} removed", removed),
+                Style::default().fg(Color::Red),
+            ),
+            Span::raw("  "),
+            Span::styled(
+                format!("~{} changed", changed),
+                Style::default().fg(Color::Yellow),
+            ),
+        ]),
+        Line::from(""),
+    ];
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(summary.len() as u16), Constraint::Min(1)])
+        .split(inner);
+    f.render_widget(Paragraph::new(Text::from(summary)), chunks[0]);
+
+    if diff.changes.is_empty() {
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                "  No differences",
+                Style::default().fg(Color::DarkGray),
+            )),
+            chunks[1],
+        );
+        return;
+    }
+
+    let selected_style = Style::default().bg(Color::DarkGray);
+    let red = Style::default().fg(Color::Red);
+    let green = Style::default().fg(Color::Green);
+
+    if diff.side_by_side {
+        let width = (chunks[1].width as usize).saturating_sub(4) * 2 / 5;
+        // One row for the header
+        let (offset, changes) = diff.visible_changes(chunks[1].height.saturating_sub(1) as usize);
+        let rows: Vec<Row> = changes
+            .iter()
+            .enumerate()
+            .map(|(i, change)| {
+                let (old, new) = match &change.kind {
+                    ChangeKind::Added(value) => (
+                        Cell::from(""),
+                        Cell::from(preview(value, width)).style(green),
+                    ),
+                    ChangeKind::Removed(value) => {
+                        (Cell::from(preview(value, width)).style(red), Cell::from(""))
+                    }
+                    ChangeKind::Changed { old, new } => (
+                        Cell::from(preview(old, width)).style(red),
+                        Cell::from(preview(new, width)).style(green),
+                    ),
+                };
+                let row = Row::new(vec![Cell::from(change.path.as_str()), old, new]);
+                if offset + i == diff.selected() {
+                    row.style(selected_style)
+                } else {
+                    row
+                }
+            })
+            .collect();
+        let header = Row::new(vec!["Path", "Old", "New"]).style(
             Style::default()
-                .fg(Color::DarkGray)
+                .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
-        )),
+        );
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(20),
+                Constraint::Percentage(40),
+                Constraint::Percentage(40),
+            ],
+        )
+        .header(header);
+        f.render_widget(table, chunks[1]);
+    } else {
+        let width = chunks[1].width as usize;
+        let (offset, changes) = diff.visible_changes(chunks[1].height as usize);
+        let lines: Vec<Line> = changes
+            .iter()
+            .enumerate()
+            .map(|(i, change)| {
+                let path = Span::styled(
+                    format!("{}  ", change.path),
+                    Style::default().fg(Color::White),
+                );
+                let spans = match &change.kind {
+                    ChangeKind::Added(value) => vec![
+                        Span::styled("  + ", green),
+                        path,
+                        Span::styled(preview(value, width), green),
+                    ],
+                    ChangeKind::Removed(value) => vec![
+                        Span::styled("  - ", red),
+                        path,
+                        Span::styled(preview(value, width), red),
+                    ],
+                    ChangeKind::Changed { old, new } => vec![
+                        Span::styled("  ~ ", Style::default().fg(Color::Yellow)),
+                        path,
+                        Span::styled(preview(old, width / 2), red),
+                        Span::styled(" → ", Style::default().fg(Color::DarkGray)),
+                        Span::styled(preview(new, width / 2), green),
+                    ],
+                };
+                let line = Line::from(spans);
+                if offset + i == diff.selected() {
+                    line.style(selected_style)
+                } else {
+                    line
+                }
+            })
+            .collect();
+        f.render_widget(Paragraph::new(Text::from(lines)), chunks[1]);
+    }
+}
+
+/// `label`, ID and creation time of one side of a diff
+fn checkpoint_line<'a>(label: &'a str, cp: Option<&'a Checkpoint>, color: Color) -> Line<'a> {
+    let (id, created) = cp
+        .map(|cp| (cp.checkpoint_id.as_str(), format_datetime(&cp.created_at)))
+        .unwrap_or_default();
+    Line::from(vec![
+        Span::styled(label, Style::default().fg(Color::DarkGray)),
+        Span::styled(id, Style::default().fg(color)),
+        Span::styled(
+            format!("  {}", created),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])
+}
+
+fn draw_timeline_modal(f: &mut Frame, app: &App) {
+    let area = centered_rect(90, 85, f.area());
+    f.render_widget(Clear, area);
+
+    let timeline = match &app.timeline {
+        Some(timeline) => timeline,
+        None => {
+            draw_loading_modal(f, area, " Timeline ", Color::Blue);
+            return;
+        }
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Blue))
+        .title(format!(" Timeline: {} ", timeline.instance_id))
+        .title(Line::from(format!(" {} ", format_gap(timeline.total()))).right_aligned());
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let (status_text, status_color) = status_style(timeline.status);
+    let retries_style = if timeline.retry_count > 0 {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default().fg(Color::White)
+    };
+    let mut summary = vec![
         Line::from(""),
+        Line::from(vec![
+            Span::styled("  Status: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(status_text, Style::default().fg(status_color)),
+            Span::styled("   Retries: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!("{} / {}", timeline.retry_count, timeline.max_retries),
+                retries_style,
+            ),
+            Span::styled("   Checkpoints: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                timeline.checkpoint_count().to_string(),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled("   Slowest step: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                timeline
+                    .slowest()
+                    .map(format_gap)
+                    .unwrap_or_else(|| "-".to_string()),
+                Style::default().fg(Color::White),
+            ),
+        ]),
     ];
+    if let Some(age) = timeline.stale_heartbeat {
+        summary.push(Line::from(Span::styled(
+            format!("  ⚠ Stale: no heartbeat for {}", format_gap(age)),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )));
+    }
+    summary.push(Line::from(""));
 
-    // Pretty print the JSON data
-    let json_str = serde_json::to_string_pretty(&checkpoint.data)
-        .unwrap_or_else(|_| format!("{:?}", checkpoint.data));
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(summary.len() as u16), Constraint::Min(1)])
+        .split(inner);
+    f.render_widget(Paragraph::new(Text::from(summary)), chunks[0]);
 
-    for line in json_str.lines() {
-        lines.push(Line::from(Span::styled(
-            format!("  {}", line),
+    // Show dates when the timeline spans several days
+    let time_format = if timeline.start.date_naive() == timeline.end.date_naive() {
+        "%H:%M:%S"
+    } else {
+        "%m-%d %H:%M:%S"
+    };
+    let time_width = if time_format.len() > 8 { 14 } else { 8 };
+    let (event_width, step_width) = (32, 9);
+    // Columns are separated by one space
+    let axis_width =
+        (chunks[1].width as usize).saturating_sub(time_width + event_width + step_width + 3);
+
+    let total_ms = timeline.total().num_milliseconds().max(1) as f64;
+    let slowest = timeline.slowest();
+
+    // One row for the header
+    let (offset, events) = timeline.visible_events(chunks[1].height.saturating_sub(1) as usize);
+    let rows: Vec<Row> = events
+        .iter()
+        .enumerate()
+        .map(|(i, event)| {
+            let (label, label_style) = event_label(timeline, event);
+
+            let share = event
+                .gap
+                .map_or(0.0, |gap| gap.num_milliseconds() as f64 / total_ms);
+            let step_color = if share >= 0.25 {
+                Color::Red
+            } else if share >= 0.10 {
+                Color::Yellow
+            } else {
+                Color::Green
+            };
+
+            let mut step_style = Style::default().fg(step_color);
+            if event.gap.is_some() && event.gap == slowest {
+                step_style = step_style.add_modifier(Modifier::BOLD);
+            }
+
+            let end = timeline.position(event.at, axis_width);
+            let bar = match (&event.kind, event.gap) {
+                (EventKind::Heartbeat, _) => {
+                    Span::styled(format!("{}♥", " ".repeat(end)), label_style)
+                }
+                (_, Some(gap)) if axis_width > 0 => {
+                    let start = timeline.position(event.at - gap, axis_width);
+                    Span::styled(
+                        format!("{}{}", " ".repeat(start), "█".repeat((end - start).max(1))),
+                        Style::default().fg(step_color),
+                    )
+                }
+                _ => Span::styled(
+                    format!("{}│", " ".repeat(end)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            };
+
+            let row = Row::new(vec![
+                Cell::from(Span::styled(
+                    event.at.format(time_format).to_string(),
+                    Style::default().fg(Color::DarkGray),
+                )),
+                Cell::from(Span::styled(truncate(&label, event_width), label_style)),
+                Cell::from(Span::styled(
+                    event.gap.map(format_gap).unwrap_or_default(),
+                    step_style,
+                )),
+                Cell::from(bar),
+            ]);
+            if offset + i == timeline.selected() {
+                row.style(Style::default().bg(Color::DarkGray))
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    // Axis from the first event to the last
+    let axis_end = format_gap(timeline.total());
+    let axis = format!(
+        "0{:>width$}",
+        axis_end,
+        width = axis_width.saturating_sub(1).max(axis_end.len())
+    );
+    let header = Row::new(vec![
+        Cell::from("Time"),
+        Cell::from("Event"),
+        Cell::from("Step"),
+        Cell::from(axis),
+    ])
+    .style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    );
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(time_width as u16),
+            Constraint::Length(event_width as u16),
+            Constraint::Length(step_width as u16),
+            Constraint::Min(0),
+        ],
+    )
+    .header(header);
+    f.render_widget(table, chunks[1]);
+}
+
+/// Text and style of the event column of a timeline row
+fn event_label(timeline: &Timeline, event: &Event) -> (String, Style) {
+    match &event.kind {
+        EventKind::Created => ("Created".to_string(), Style::default().fg(Color::White)),
+        EventKind::Started if timeline.retry_count > 0 => (
+            format!(
+                "Started (retry {} of {})",
+                timeline.retry_count, timeline.max_retries
+            ),
+            Style::default().fg(Color::Yellow),
+        ),
+        EventKind::Started => ("Started".to_string(), Style::default().fg(Color::White)),
+        EventKind::Checkpoint { id, size } => (
+            format!("{} ({})", id, format_bytes(*size)),
             Style::default().fg(Color::Cyan),
-        )));
+        ),
+        EventKind::Heartbeat if timeline.stale_heartbeat.is_some() => (
+            "Last heartbeat (stale)".to_string(),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        EventKind::Heartbeat => (
+            "Last heartbeat".to_string(),
+            Style::default().fg(Color::Magenta),
+        ),
+        EventKind::Finished(status) => {
+            let (text, color) = status_style(*status);
+            (text.to_string(), Style::default().fg(color))
+        }
+        EventKind::Now => ("Now".to_string(), Style::default().fg(Color::DarkGray)),
     }
+}
 
-    let paragraph = Paragraph::new(Text::from(lines))
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Yellow))
-                .title(format!(
-                    " Checkpoint: {} ",
-                    truncate(&checkpoint.checkpoint_id, 30)
-                )),
+/// Duration of a timeline step, with milliseconds below one second
+fn format_gap(gap: chrono::Duration) -> String {
+    let ms = gap.num_milliseconds().max(0) as u64;
+    if ms < 1000 {
+        format!("{}ms", ms)
+    } else {
+        format_duration(ms)
+    }
+}
+
+/// Draw a JSON tree below a titled rule, with the query line on top while a
+/// query is open or active
+fn draw_json_tree(f: &mut Frame, app: &App, tree: &JsonTree, title: &str, area: Rect) {
+    let rows = tree.rows();
+    let position = if rows.is_empty() {
+        String::new()
+    } else {
+        format!(" {}/{} ", tree.selected() + 1, rows.len())
+    };
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(Line::from(vec![
+            Span::styled(
+                title,
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(tree.summary(), Style::default().fg(Color::DarkGray)),
+            Span::raw(" "),
+        ]))
+        .title(Line::from(position).right_aligned());
+    let mut inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let export_prompt = app
+        .prompt
+        .as_ref()
+        .filter(|prompt| matches!(prompt.kind, PromptKind::Export | PromptKind::ExportBundle));
+    let prompt = app
+        .prompt
+        .as_ref()
+        .filter(|prompt| prompt.kind == PromptKind::Query);
+    if let Some(export_prompt) = export_prompt {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(inner);
+        draw_export_line(f, app, export_prompt, chunks[0]);
+        inner = chunks[1];
+    } else if prompt.is_some() || tree.query().is_some() {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(inner);
+        draw_query_line(f, tree, prompt, chunks[0]);
+        inner = chunks[1];
+    }
+
+    if rows.is_empty() {
+        let text = if tree.query().is_some() {
+            "  No matches"
+        } else {
+            "  (empty)"
+        };
+        f.render_widget(
+            Paragraph::new(Span::styled(text, Style::default().fg(Color::DarkGray))),
+            inner,
+        );
+        return;
+    }
+
+    let (offset, visible) = tree.visible_rows(inner.height as usize);
+    let lines: Vec<Line> = visible
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut line = json_row_line(row);
+            if offset + i == tree.selected() {
+                line = line.style(Style::default().bg(Color::DarkGray));
+            }
+            line
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(Text::from(lines)), inner);
+}
+
+/// The open query prompt with its match count or error, or the active query
+fn draw_query_line(f: &mut Frame, tree: &JsonTree, prompt: Option<&Prompt>, area: Rect) {
+    let label = " Query> ";
+    let (text, status) = match prompt {
+        Some(prompt) => (
+            prompt.input.value(),
+            match &prompt.error {
+                Some(e) => Span::styled(format!("  {}", e), Style::default().fg(Color::Red)),
+                None => Span::styled(
+                    format!("  {}", tree.summary()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            },
+        ),
+        None => (
+            tree.query().map(|query| query.text.as_str()).unwrap_or(""),
+            Span::styled(
+                format!("  {} (Esc to clear)", tree.summary()),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ),
+    };
+
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(label, Style::default().fg(Color::Yellow)),
+            Span::raw(text),
+            status,
+        ])),
+        area,
+    );
+
+    if let Some(prompt) = prompt {
+        let cursor_x = area.x + (label.chars().count() + prompt.input.cursor()) as u16;
+        f.set_cursor_position((cursor_x.min(area.right().saturating_sub(1)), area.y));
+    }
+}
+
+/// The open export prompt with a hint on the formats
+fn draw_export_line(f: &mut Frame, app: &App, prompt: &Prompt, area: Rect) {
+    let (label, hint) = if prompt.kind == PromptKind::ExportBundle {
+        (
+            " Export bundle to> ".to_string(),
+            "  directory, or .tar / .tar.gz",
         )
-        .scroll((app.detail_scroll, 0))
-        .wrap(Wrap { trim: false });
+    } else {
+        (
+            format!(" Export {} to> ", app.export_part.as_str()),
+            "  .json pretty, .ndjson one line per item | Tab:Part",
+        )
+    };
 
-    f.render_widget(paragraph, area);
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(label.as_str(), Style::default().fg(Color::Yellow)),
+            Span::raw(prompt.input.value()),
+            Span::styled(hint, Style::default().fg(Color::DarkGray)),
+        ])),
+        area,
+    );
+
+    let cursor_x = area.x + (label.chars().count() + prompt.input.cursor()) as u16;
+    f.set_cursor_position((cursor_x.min(area.right().saturating_sub(1)), area.y));
+}
+
+/// One tree row: indent, fold marker, key and syntax-coloured value
+fn json_row_line(row: &TreeRow) -> Line<'_> {
+    let marker = match row.kind {
+        RowKind::Object { expanded: true, .. } | RowKind::Array { expanded: true, .. } => "▾ ",
+        RowKind::Object { .. } | RowKind::Array { .. } => "▸ ",
+        _ => "  ",
+    };
+    let mut spans = vec![
+        Span::raw("  ".repeat(row.depth + 1)),
+        Span::styled(marker, Style::default().fg(Color::DarkGray)),
+    ];
+
+    match &row.key {
+        Some(Key::Field(name)) => {
+            spans.push(Span::styled(
+                name.as_str(),
+                Style::default().fg(Color::Blue),
+            ));
+            spans.push(Span::raw(": "));
+        }
+        Some(Key::Index(index)) => {
+            spans.push(Span::styled(
+                format!("[{}]", index),
+                Style::default().fg(Color::DarkGray),
+            ));
+            spans.push(Span::raw(": "));
+        }
+        Some(Key::Path(path)) => {
+            spans.push(Span::styled(
+                path.as_str(),
+                Style::default().fg(Color::Yellow),
+            ));
+            spans.push(Span::raw(": "));
+        }
+        None => {}
+    }
+
+    spans.push(match &row.kind {
+        RowKind::Object { len, .. } => Span::styled(
+            container_summary(false, *len),
+            Style::default().fg(Color::DarkGray),
+        ),
+        RowKind::Array { len, .. } => Span::styled(
+            container_summary(true, *len),
+            Style::default().fg(Color::DarkGray),
+        ),
+        RowKind::String(s) => Span::styled(s.as_str(), Style::default().fg(Color::Green)),
+        RowKind::Number(n) => Span::styled(n.as_str(), Style::default().fg(Color::Cyan)),
+        RowKind::Bool(b) => Span::styled(b.to_string(), Style::default().fg(Color::Yellow)),
+        RowKind::Null => Span::styled("null", Style::default().fg(Color::Magenta)),
+    });
+
+    Line::from(spans)
+}
+
+fn draw_confirm_popup(f: &mut Frame, pending: &PendingAction) {
+    let area = centered_rect(50, 20, f.area());
+    f.render_widget(Clear, area);
+
+    let dialog = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw(format!("{} instance ", pending.action.as_str())),
+            Span::styled(
+                &pending.instance_id,
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("?"),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "y:Confirm | n:Cancel",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(" Confirm "),
+    )
+    .wrap(Wrap { trim: false })
+    .centered();
+
+    f.render_widget(dialog, area);
+}
+
+fn draw_profile_picker(f: &mut Frame, app: &App, selected: usize) {
+    let area = centered_rect(40, 50, f.area());
+    f.render_widget(Clear, area);
+
+    let mut lines = vec![Line::from("")];
+    for (i, name) in app.profiles.iter().enumerate() {
+        let style = if i == selected {
+            Style::default().bg(Color::DarkGray)
+        } else {
+            Style::default()
+        };
+        let active = if app.profile.as_ref() == Some(name) {
+            " (active)"
+        } else {
+            ""
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {}", name), style.fg(Color::Cyan)),
+            Span::styled(active, style.fg(Color::DarkGray)),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Enter:Switch | Esc:Close",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let picker = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(" Profiles "),
+    );
+
+    f.render_widget(picker, area);
+}
+
+fn draw_tenant_selector(f: &mut Frame, app: &App, prompt: &Prompt) {
+    let area = centered_rect(40, 50, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" Tenant Scope ");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    if inner.height < 3 {
+        return;
+    }
+
+    // Input line
+    let label = " Tenant> ";
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled(label, Style::default().fg(Color::Yellow)),
+        Span::raw(prompt.input.value()),
+    ]));
+    f.render_widget(input, Rect { height: 1, ..inner });
+    let cursor_x = inner.x + (label.chars().count() + prompt.input.cursor()) as u16;
+    f.set_cursor_position((cursor_x.min(inner.right().saturating_sub(1)), inner.y));
+
+    // Choices below a blank line, keeping the selected one in view
+    let list = Rect {
+        y: inner.y + 2,
+        height: inner.height - 2,
+        ..inner
+    };
+    let choices = &app.tenant_choices;
+    let height = list.height as usize;
+    let offset = choices.selected.saturating_sub(height.saturating_sub(1));
+    let lines: Vec<Line> = choices
+        .rows
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(height)
+        .map(|(i, row)| {
+            let style = if i == choices.selected {
+                Style::default().bg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
+            let (text, note) = match row {
+                TenantChoice::All => ("All tenants".to_string(), ""),
+                TenantChoice::Known(tenant) => (tenant.clone(), ""),
+                TenantChoice::Entered(tenant) => (tenant.clone(), " (new)"),
+            };
+            let current = match row {
+                TenantChoice::All => app.tenant_id.is_none(),
+                TenantChoice::Known(tenant) => app.tenant_id.as_ref() == Some(tenant),
+                TenantChoice::Entered(_) => false,
+            };
+            Line::from(vec![
+                Span::styled(format!("  {}", text), style.fg(Color::Cyan)),
+                Span::styled(note, style.fg(Color::Yellow)),
+                Span::styled(
+                    if current { " (current)" } else { "" },
+                    style.fg(Color::DarkGray),
+                ),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), list);
+}
+
+fn draw_tenant_picker(f: &mut Frame, app: &App, picker: &TenantPicker) {
+    let area = centered_rect(40, 50, f.area());
+    f.render_widget(Clear, area);
+
+    // Keep the selected tenant in view; less the borders, padding and hint
+    let height = (area.height as usize).saturating_sub(5).max(1);
+    let offset = picker.selected.saturating_sub(height - 1);
+
+    let mut lines = vec![Line::from("")];
+    for (i, tenant) in picker.tenants.iter().enumerate().skip(offset).take(height) {
+        let style = if i == picker.selected {
+            Style::default().bg(Color::DarkGray)
+        } else {
+            Style::default()
+        };
+        let mark = if picker.marked.contains(tenant) {
+            "● "
+        } else {
+            "  "
+        };
+        let active = if app.compare_tenants.is_empty() && app.metrics_tenant() == Some(tenant) {
+            " (shown)"
+        } else {
+            ""
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {}", mark), style.fg(Color::Magenta)),
+            Span::styled(tenant.clone(), style.fg(Color::Cyan)),
+            Span::styled(active, style.fg(Color::DarkGray)),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Enter:Show | Space:Mark | c:Compare marked | Esc:Close",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let picker = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(" Tenants "),
+    );
+
+    f.render_widget(picker, area);
+}
+
+fn draw_range_picker(f: &mut Frame, app: &App, selected: usize) {
+    let area = centered_rect(40, 50, f.area());
+    f.render_widget(Clear, area);
+
+    let presets = MetricsRange::presets();
+    let custom = match app.metrics_range {
+        range if presets.contains(&range) => "Custom…".to_string(),
+        range => format!("Custom… ({})", range.text()),
+    };
+    let labels = presets
+        .iter()
+        .map(|range| range.label())
+        .chain(std::iter::once(custom));
+
+    let mut lines = vec![Line::from("")];
+    for (i, label) in labels.enumerate() {
+        let style = if i == selected {
+            Style::default().bg(Color::DarkGray)
+        } else {
+            Style::default()
+        };
+        let active = if presets.get(i) == Some(&app.metrics_range) {
+            " (active)"
+        } else {
+            ""
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {}", label), style.fg(Color::Cyan)),
+            Span::styled(active, style.fg(Color::DarkGray)),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Enter:Select | Esc:Close",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let picker = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(" Metrics Range "),
+    );
+
+    f.render_widget(picker, area);
+}
+
+/// Placeholder for a modal whose data is still being fetched
+fn draw_loading_modal(f: &mut Frame, area: Rect, title: &str, border: Color) {
+    let loading = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "  Loading…",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border))
+            .title(title.to_string()),
+    );
+
+    f.render_widget(loading, area);
 }
 
 fn draw_error_popup(f: &mut Frame, error: &str) {
@@ -876,6 +2768,69 @@ fn draw_error_popup(f: &mut Frame, error: &str) {
     f.render_widget(error_block, area);
 }
 
+/// Width of the server column
+const SERVER_WIDTH: u16 = 16;
+
+/// Draw `table` scrolled to keep the cursor in view, with a scrollbar when
+/// its `len` rows do not fit
+fn draw_table(
+    f: &mut Frame,
+    table: Table,
+    area: Rect,
+    viewport: &Viewport,
+    selected: usize,
+    len: usize,
+) {
+    let table = table.row_highlight_style(Style::default().bg(Color::DarkGray));
+    let mut state = viewport.state(selected);
+    f.render_stateful_widget(table, area, &mut state);
+    // Less the borders and the header row
+    let height = area.height.saturating_sub(3) as usize;
+    viewport.save(&state, height);
+
+    if len > height {
+        let mut scrollbar = ScrollbarState::new(len)
+            .viewport_content_length(height)
+            .position(selected.min(len - 1));
+        f.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None),
+            // Beside the data rows, on the right border
+            Rect {
+                y: area.y + 2,
+                height: height as u16,
+                ..area
+            },
+            &mut scrollbar,
+        );
+    }
+}
+
+/// Bold header cell of a sortable column, with an arrow when sorted by it
+fn sort_header<C: SortColumn>(sort: &Sort<C>, column: C) -> Cell<'static> {
+    Cell::from(format!("{}{}", column.as_str(), sort.arrow(column)))
+        .style(Style::default().add_modifier(Modifier::BOLD))
+}
+
+/// Server name cell, red while the server is unreachable
+fn server_cell(app: &App, index: usize) -> Cell<'static> {
+    let server = &app.servers[index];
+    let color = match server.connection {
+        ConnectionState::Connected { .. } => Color::Cyan,
+        _ => Color::Red,
+    };
+    Cell::from(truncate(&server.name, SERVER_WIDTH as usize)).style(Style::default().fg(color))
+}
+
+/// Title suffix naming the focused server, if any
+fn focus_suffix(app: &App) -> String {
+    match app.focus {
+        Some(i) => format!(" @ {}", app.servers[i].name),
+        None => String::new(),
+    }
+}
+
 /// Helper to create a centered rect
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
@@ -897,28 +2852,48 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
-/// Truncate a string to a maximum length
+/// Describe the rows of a page, e.g. "rows 101–200 of 523"
+fn rows_label(offset: u32, len: usize, total: u32) -> String {
+    if len == 0 {
+        format!("no rows of {}", total)
+    } else {
+        format!("rows {}–{} of {}", offset + 1, offset as usize + len, total)
+    }
+}
+
+/// Title suffix describing the load state of a view
+fn state_suffix(state: LoadState) -> String {
+    state
+        .label()
+        .map(|label| format!(" [{}]", label))
+        .unwrap_or_default()
+}
+
+/// Truncate a string to a maximum length in characters
 fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
+    if s.chars().count() <= max_len {
         s.to_string()
     } else {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
+        let kept: String = s.chars().take(max_len.saturating_sub(3)).collect();
+        format!("{}...", kept)
     }
 }
 
-/// Format bytes to human-readable size
-fn format_bytes(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if bytes >= GB {
-        format!("{:.1} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.1} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.1} KB", bytes as f64 / KB as f64)
-    } else {
-        format!("{} B", bytes)
+    #[test]
+    fn truncate_keeps_short_strings() {
+        assert_eq!(truncate("prod", 8), "prod");
+        assert_eq!(truncate("zürich-1", 8), "zürich-1");
+    }
+
+    #[test]
+    fn truncate_cuts_by_characters() {
+        assert_eq!(truncate("abcdefghij", 8), "abcde...");
+        // A byte cut at 5 would land inside `ü`
+        assert_eq!(truncate("zzzzüberwald", 8), "zzzzü...");
+        assert_eq!(truncate("東京サーバー本番", 6), "東京サ...");
     }
 }
@@ -0,0 +1,763 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Background data fetching.
+//!
+//! Network calls never run on the UI task. The UI sends a [`Request`] to the
+//! [`Worker`], which performs the SDK calls on a spawned tokio task and reports
+//! each result back as an [`Update`] over a channel. All requests share one
+//! long-lived [`Connection`].
+
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use runtara_management_sdk::{
+    Checkpoint, CheckpointSummary, GetTenantMetricsOptions, HealthStatus, InstanceInfo,
+    InstanceStatus, InstanceSummary, ListCheckpointsOptions, ListCheckpointsResult,
+    ListImagesOptions, ListImagesResult, ListInstancesOptions, ListInstancesOrder,
+    ListInstancesResult, ManagementSdk, MetricsGranularity, SdkConfig, SdkError,
+    StartInstanceOptions, TenantMetricsResult,
+};
+use serde_json::Value;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::mpsc::error::SendError;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
+
+use crate::app::{InstanceAction, PAGE_SIZE};
+use crate::connection::{Connection, ConnectionState};
+use crate::export::{self, Bundle};
+use crate::filter::InstanceFilter;
+use crate::image_metrics::IMAGE_METRICS_LIMIT;
+use crate::range::MetricsRange;
+
+/// Instance lookups in flight at once while scanning running instances
+const SCAN_CONCURRENCY: usize = 8;
+
+/// Parameters for a full data refresh.
+#[derive(Debug, Clone)]
+pub struct RefreshParams {
+    pub tenant_id: Option<String>,
+    /// Tenant whose metrics are fetched
+    pub metrics_tenant: Option<String>,
+    /// Tenants whose metrics are compared side by side
+    pub compare_tenants: Vec<String>,
+    pub status: Option<InstanceStatus>,
+    pub instance_filter: InstanceFilter,
+    /// Server-side order of the instances, when sorted by a time column
+    pub instance_order: Option<ListInstancesOrder>,
+    pub instances_offset: u32,
+    pub images_offset: u32,
+    pub granularity: MetricsGranularity,
+    pub metrics_range: MetricsRange,
+    /// Whether to scan the running instances for their heartbeat
+    pub scan_running: bool,
+    /// Tag of the lists and metrics reported back
+    pub seq: u64,
+}
+
+/// Running instances with their last sign of life.
+#[derive(Debug)]
+pub struct RunningScan {
+    /// Instance and its last heartbeat, or its start when it has none yet
+    pub instances: Vec<(InstanceSummary, DateTime<Utc>)>,
+    /// Running instances in total; only the first page is scanned
+    pub total: u32,
+}
+
+/// Newest instances of one image in a time range.
+#[derive(Debug)]
+pub struct ImageInstances {
+    pub instances: Vec<InstanceSummary>,
+    /// Instances in the range in total; at most [`IMAGE_METRICS_LIMIT`] are
+    /// listed
+    pub total: u32,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+/// Work requested by the UI.
+#[derive(Debug, Clone)]
+pub enum Request {
+    /// Refresh health, instances, images and metrics
+    Refresh(RefreshParams),
+    /// Fetch full details of one instance
+    InstanceDetail { seq: u64, instance_id: String },
+    /// Fetch a page of checkpoints of one instance
+    Checkpoints {
+        seq: u64,
+        instance_id: String,
+        offset: u32,
+    },
+    /// Fetch the data of one checkpoint
+    CheckpointDetail {
+        seq: u64,
+        instance_id: String,
+        checkpoint_id: String,
+    },
+    /// Poll a followed instance: its details and checkpoints created after
+    /// `since`
+    Follow {
+        seq: u64,
+        instance_id: String,
+        since: Option<DateTime<Utc>>,
+    },
+    /// Fetch all checkpoints of one instance for its timeline
+    Timeline { seq: u64, instance_id: String },
+    /// Fetch the instances of one image created between `start` and `end`
+    ImageInstances {
+        seq: u64,
+        tenant_id: Option<String>,
+        image_id: String,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
+    /// Apply a lifecycle action to an instance
+    Action {
+        action: InstanceAction,
+        instance_id: String,
+    },
+    /// Write an instance with all its checkpoints to `path`
+    ExportBundle { instance_id: String, path: PathBuf },
+    /// Write a detail payload to `path`
+    ExportPayload { path: PathBuf, value: Value },
+}
+
+/// Result reported back to the UI.
+///
+/// Drill-down results carry the `seq` of the request that produced them so the
+/// UI can drop answers for views the user has already left. Lists and metrics
+/// carry the `seq` of their refresh, so a page or filter changed while it was
+/// in flight does not label its results.
+#[derive(Debug)]
+pub enum Update {
+    /// Connection state changed
+    Connection(ConnectionState),
+    /// Not connected; the refresh was skipped
+    Disconnected,
+    Health(Result<HealthStatus, String>),
+    Instances {
+        seq: u64,
+        result: Result<ListInstancesResult, String>,
+    },
+    /// Heartbeats of running instances
+    Running(Result<RunningScan, String>),
+    Images {
+        seq: u64,
+        result: Result<ListImagesResult, String>,
+    },
+    /// Metrics result, or `None` when no tenant is selected
+    Metrics {
+        seq: u64,
+        result: Option<Result<TenantMetricsResult, String>>,
+    },
+    /// Metrics of each compared tenant, in the requested order
+    Comparison {
+        seq: u64,
+        result: Vec<(String, Result<TenantMetricsResult, String>)>,
+    },
+    /// All parts of a refresh have been reported
+    RefreshDone,
+    InstanceDetail {
+        seq: u64,
+        result: Result<Box<InstanceInfo>, String>,
+    },
+    Checkpoints {
+        seq: u64,
+        result: Result<ListCheckpointsResult, String>,
+    },
+    CheckpointDetail {
+        seq: u64,
+        result: Result<Checkpoint, String>,
+    },
+    /// Details of a followed instance and its newest checkpoints, newest
+    /// first
+    Follow {
+        seq: u64,
+        result: Result<(Box<InstanceInfo>, Vec<CheckpointSummary>), String>,
+    },
+    /// All checkpoints of an instance, oldest first
+    Timeline {
+        seq: u64,
+        result: Result<Vec<CheckpointSummary>, String>,
+    },
+    ImageInstances {
+        seq: u64,
+        result: Result<ImageInstances, String>,
+    },
+    /// Outcome of a lifecycle action, as a message for the user
+    ActionDone {
+        action: InstanceAction,
+        instance_id: String,
+        result: Result<String, String>,
+    },
+    /// Outcome of a bundle export, as a message for the user
+    ExportDone(Result<String, String>),
+}
+
+/// Update from the worker of one server.
+#[derive(Debug)]
+pub struct ServerUpdate {
+    /// Index of the server in the app
+    pub server: usize,
+    pub update: Update,
+}
+
+/// Sends updates tagged with the server they came from.
+#[derive(Clone)]
+struct Reporter {
+    server: usize,
+    updates: UnboundedSender<ServerUpdate>,
+}
+
+impl Reporter {
+    fn send(&self, update: Update) -> Result<(), SendError<ServerUpdate>> {
+        self.updates.send(ServerUpdate {
+            server: self.server,
+            update,
+        })
+    }
+}
+
+/// Handle for running requests in the background.
+///
+/// Dropping the worker stops its connection supervisor.
+pub struct Worker {
+    connection: Arc<Connection>,
+    updates: Reporter,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl Worker {
+    /// Create the worker of server number `server`
+    pub fn new(
+        server: usize,
+        config: SdkConfig,
+        addrs: &[SocketAddr],
+        updates: UnboundedSender<ServerUpdate>,
+    ) -> Result<Self, SdkError> {
+        let (connection, supervisor) = Connection::start(config, addrs)?;
+        let updates = Reporter { server, updates };
+
+        // Forward connection state changes to the UI
+        let mut state = connection.subscribe();
+        let state_updates = updates.clone();
+        let forwarder = tokio::spawn(async move {
+            while state.changed().await.is_ok() {
+                let current = state.borrow_and_update().clone();
+                if state_updates.send(Update::Connection(current)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            connection,
+            updates,
+            tasks: vec![supervisor, forwarder],
+        })
+    }
+
+    /// Run a request on a background task
+    pub fn send(&self, request: Request) {
+        let connection = self.connection.clone();
+        let updates = self.updates.clone();
+        tokio::spawn(async move {
+            run(&connection, request, &updates).await;
+        });
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+async fn run(connection: &Connection, request: Request, updates: &Reporter) {
+    // Writing a payload needs no connection
+    if let Request::ExportPayload { path, value } = request {
+        let result = export_payload(path, value).await;
+        let _ = updates.send(Update::ExportDone(result));
+        return;
+    }
+
+    if let Err(e) = connection.sdk().await {
+        let update = match request {
+            Request::Refresh(_) => Update::Disconnected,
+            Request::InstanceDetail { seq, .. } => Update::InstanceDetail {
+                seq,
+                result: Err(e),
+            },
+            Request::Checkpoints { seq, .. } => Update::Checkpoints {
+                seq,
+                result: Err(e),
+            },
+            Request::CheckpointDetail { seq, .. } => Update::CheckpointDetail {
+                seq,
+                result: Err(e),
+            },
+            Request::Follow { seq, .. } => Update::Follow {
+                seq,
+                result: Err(e),
+            },
+            Request::Timeline { seq, .. } => Update::Timeline {
+                seq,
+                result: Err(e),
+            },
+            Request::ImageInstances { seq, .. } => Update::ImageInstances {
+                seq,
+                result: Err(e),
+            },
+            Request::Action {
+                action,
+                instance_id,
+            } => Update::ActionDone {
+                action,
+                instance_id,
+                result: Err(e),
+            },
+            Request::ExportBundle { .. } => {
+                Update::ExportDone(Err(format!("Export failed: {}", e)))
+            }
+            Request::ExportPayload { .. } => unreachable!(),
+        };
+        let _ = updates.send(update);
+        return;
+    }
+
+    match request {
+        Request::Refresh(params) => refresh(connection, params, updates).await,
+        Request::InstanceDetail { seq, instance_id } => {
+            let result = call(connection, |sdk| sdk.get_instance_status(&instance_id))
+                .await
+                .map(Box::new)
+                .map_err(|e| format!("Failed to get instance details: {}", e));
+            let _ = updates.send(Update::InstanceDetail { seq, result });
+        }
+        Request::Checkpoints {
+            seq,
+            instance_id,
+            offset,
+        } => {
+            let options = ListCheckpointsOptions::new()
+                .with_limit(PAGE_SIZE)
+                .with_offset(offset);
+            let result = call(connection, |sdk| {
+                sdk.list_checkpoints(&instance_id, options)
+            })
+            .await
+            .map_err(|e| format!("Failed to list checkpoints: {}", e));
+            let _ = updates.send(Update::Checkpoints { seq, result });
+        }
+        Request::CheckpointDetail {
+            seq,
+            instance_id,
+            checkpoint_id,
+        } => {
+            let result = match call(connection, |sdk| {
+                sdk.get_checkpoint(&instance_id, &checkpoint_id)
+            })
+            .await
+            {
+                Ok(Some(checkpoint)) => Ok(checkpoint),
+                Ok(None) => Err("Checkpoint not found".to_string()),
+                Err(e) => Err(format!("Failed to get checkpoint: {}", e)),
+            };
+            let _ = updates.send(Update::CheckpointDetail { seq, result });
+        }
+        Request::Follow {
+            seq,
+            instance_id,
+            since,
+        } => {
+            let result = poll_instance(connection, &instance_id, since).await;
+            let _ = updates.send(Update::Follow { seq, result });
+        }
+        Request::Timeline { seq, instance_id } => {
+            let result = list_all_checkpoints(connection, &instance_id).await;
+            let _ = updates.send(Update::Timeline { seq, result });
+        }
+        Request::ImageInstances {
+            seq,
+            tenant_id,
+            image_id,
+            start,
+            end,
+        } => {
+            let result = list_image_instances(connection, tenant_id, image_id, start, end).await;
+            let _ = updates.send(Update::ImageInstances { seq, result });
+        }
+        Request::Action {
+            action,
+            instance_id,
+        } => {
+            let result = run_action(connection, action, &instance_id)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = updates.send(Update::ActionDone {
+                action,
+                instance_id,
+                result,
+            });
+        }
+        Request::ExportBundle { instance_id, path } => {
+            let result = export_bundle(connection, &instance_id, path).await;
+            let _ = updates.send(Update::ExportDone(result));
+        }
+        Request::ExportPayload { .. } => unreachable!(),
+    }
+}
+
+/// Write a payload to `path` on the blocking pool
+async fn export_payload(path: PathBuf, value: Value) -> Result<String, String> {
+    tokio::task::spawn_blocking(move || {
+        export::write_payload(&path, &value)
+            .map(|()| format!("Exported to {}", path.display()))
+            .map_err(|e| format!("Export failed: {:#}", e))
+    })
+    .await
+    .map_err(|e| format!("Export failed: {}", e))?
+}
+
+/// Fetch an instance with all its checkpoints and write it to `path`
+async fn export_bundle(
+    connection: &Connection,
+    instance_id: &str,
+    path: PathBuf,
+) -> Result<String, String> {
+    let info = call(connection, |sdk| sdk.get_instance_status(instance_id))
+        .await
+        .map_err(|e| format!("Failed to get instance details: {}", e))?;
+
+    let summaries = list_all_checkpoints(connection, instance_id).await?;
+    let mut checkpoints = Vec::with_capacity(summaries.len());
+    for summary in &summaries {
+        let checkpoint = call(connection, |sdk| {
+            sdk.get_checkpoint(instance_id, &summary.checkpoint_id)
+        })
+        .await
+        .map_err(|e| format!("Failed to get checkpoint: {}", e))?;
+        // Checkpoints can be deleted while the export runs
+        checkpoints.extend(checkpoint);
+    }
+
+    let count = checkpoints.len();
+    let bundle = Bundle { info, checkpoints };
+    let target = path.clone();
+    tokio::task::spawn_blocking(move || export::write_bundle(&target, &bundle))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("Export failed: {:#}", e))?;
+    Ok(format!(
+        "Exported {} with {} checkpoints to {}",
+        instance_id,
+        count,
+        path.display()
+    ))
+}
+
+/// Fetch the details of an instance and its checkpoints created after
+/// `since`, newest first.
+///
+/// After the first poll every page is fetched, so no new checkpoint is
+/// missed however many were created since; the first poll only needs the
+/// newest.
+async fn poll_instance(
+    connection: &Connection,
+    instance_id: &str,
+    since: Option<DateTime<Utc>>,
+) -> Result<(Box<InstanceInfo>, Vec<CheckpointSummary>), String> {
+    let info = call(connection, |sdk| sdk.get_instance_status(instance_id))
+        .await
+        .map_err(|e| format!("Failed to get instance details: {}", e))?;
+    let mut checkpoints = Vec::new();
+    loop {
+        let mut options = ListCheckpointsOptions::new()
+            .with_limit(PAGE_SIZE)
+            .with_offset(checkpoints.len() as u32);
+        options.created_after = since;
+        let page = call(connection, |sdk| sdk.list_checkpoints(instance_id, options))
+            .await
+            .map_err(|e| format!("Failed to list checkpoints: {}", e))?;
+        let fetched = page.checkpoints.len();
+        checkpoints.extend(page.checkpoints);
+        if since.is_none() || fetched == 0 || checkpoints.len() >= page.total_count as usize {
+            break;
+        }
+    }
+    Ok((Box::new(info), checkpoints))
+}
+
+/// List every page of checkpoints of an instance, oldest first
+async fn list_all_checkpoints(
+    connection: &Connection,
+    instance_id: &str,
+) -> Result<Vec<CheckpointSummary>, String> {
+    let mut summaries = Vec::new();
+    loop {
+        let options = ListCheckpointsOptions::new()
+            .with_limit(PAGE_SIZE)
+            .with_offset(summaries.len() as u32);
+        let page = call(connection, |sdk| sdk.list_checkpoints(instance_id, options))
+            .await
+            .map_err(|e| format!("Failed to list checkpoints: {}", e))?;
+        let fetched = page.checkpoints.len();
+        summaries.extend(page.checkpoints);
+        if fetched == 0 || summaries.len() >= page.total_count as usize {
+            break;
+        }
+    }
+    summaries.sort_by_key(|summary| summary.created_at);
+    Ok(summaries)
+}
+
+/// List the newest instances of an image created between `start` and `end`,
+/// up to [`IMAGE_METRICS_LIMIT`]
+async fn list_image_instances(
+    connection: &Connection,
+    tenant_id: Option<String>,
+    image_id: String,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<ImageInstances, String> {
+    let mut instances = Vec::new();
+    loop {
+        let options = ListInstancesOptions {
+            tenant_id: tenant_id.clone(),
+            image_id: Some(image_id.clone()),
+            created_after: Some(start),
+            created_before: Some(end),
+            limit: PAGE_SIZE,
+            offset: instances.len() as u32,
+            ..Default::default()
+        };
+        let page = call(connection, |sdk| sdk.list_instances(options))
+            .await
+            .map_err(|e| format!("Failed to list instances: {}", e))?;
+        let fetched = page.instances.len();
+        instances.extend(page.instances);
+        if fetched == 0
+            || instances.len() >= page.total_count as usize
+            || instances.len() >= IMAGE_METRICS_LIMIT
+        {
+            instances.truncate(IMAGE_METRICS_LIMIT);
+            return Ok(ImageInstances {
+                instances,
+                total: page.total_count,
+                start,
+                end,
+            });
+        }
+    }
+}
+
+/// Apply a lifecycle action, returning a message describing the outcome
+async fn run_action(
+    connection: &Connection,
+    action: InstanceAction,
+    instance_id: &str,
+) -> Result<String, SdkError> {
+    match action {
+        InstanceAction::Cancel => {
+            call(connection, |sdk| {
+                sdk.cancel_instance(instance_id, Some("Cancelled from runtara-tui"))
+            })
+            .await?;
+            Ok(format!("Cancel requested for {}", instance_id))
+        }
+        InstanceAction::Suspend => {
+            call(connection, |sdk| sdk.pause_instance(instance_id)).await?;
+            Ok(format!("Suspend requested for {}", instance_id))
+        }
+        InstanceAction::Resume => {
+            call(connection, |sdk| sdk.resume_instance(instance_id)).await?;
+            Ok(format!("Resumed {}", instance_id))
+        }
+        InstanceAction::Rerun => {
+            // Start a fresh instance from the original image and input
+            let info = call(connection, |sdk| sdk.get_instance_status(instance_id)).await?;
+            // Starting without it could run a workflow that needs input
+            let Some(input) = info.input else {
+                return Err(SdkError::InvalidInput(
+                    "original input unavailable".to_string(),
+                ));
+            };
+            let options =
+                StartInstanceOptions::new(info.image_id, info.tenant_id).with_input(input);
+            let result = call(connection, |sdk| sdk.start_instance(options)).await?;
+            if !result.success {
+                return Err(SdkError::Server {
+                    code: "START_FAILED".to_string(),
+                    message: result.error.unwrap_or_default(),
+                });
+            }
+            Ok(format!("Re-ran {} as {}", instance_id, result.instance_id))
+        }
+    }
+}
+
+/// Fetch all list data, reporting each part as soon as it arrives
+async fn refresh(connection: &Connection, params: RefreshParams, updates: &Reporter) {
+    let health = call(connection, |sdk| sdk.health_check())
+        .await
+        .map_err(|e| format!("Health check failed: {}", e));
+    let _ = updates.send(Update::Health(health));
+
+    let mut options = instances_options(
+        params.tenant_id.clone(),
+        params.status,
+        &params.instance_filter,
+        PAGE_SIZE,
+        params.instances_offset,
+    );
+    options.order_by = params.instance_order;
+    let result = call(connection, |sdk| sdk.list_instances(options))
+        .await
+        .map_err(|e| format!("Failed to list instances: {}", e));
+    let _ = updates.send(Update::Instances {
+        seq: params.seq,
+        result,
+    });
+
+    let options = ListImagesOptions {
+        tenant_id: params.tenant_id.clone(),
+        limit: PAGE_SIZE,
+        offset: params.images_offset,
+    };
+    let result = call(connection, |sdk| sdk.list_images(options))
+        .await
+        .map_err(|e| format!("Failed to list images: {}", e));
+    let _ = updates.send(Update::Images {
+        seq: params.seq,
+        result,
+    });
+
+    // Metrics require a tenant
+    let result = match params.metrics_tenant {
+        Some(ref tenant_id) => Some(tenant_metrics(connection, tenant_id, &params).await),
+        None => None,
+    };
+    let _ = updates.send(Update::Metrics {
+        seq: params.seq,
+        result,
+    });
+
+    let mut comparison = Vec::new();
+    for tenant_id in &params.compare_tenants {
+        let result = tenant_metrics(connection, tenant_id, &params).await;
+        comparison.push((tenant_id.clone(), result));
+    }
+    let _ = updates.send(Update::Comparison {
+        seq: params.seq,
+        result: comparison,
+    });
+
+    // Last, as it takes a lookup per running instance
+    if params.scan_running {
+        let running = scan_running(connection, params.tenant_id.clone()).await;
+        let _ = updates.send(Update::Running(running));
+    }
+
+    let _ = updates.send(Update::RefreshDone);
+}
+
+/// Fetch the metrics of one tenant over the range of a refresh
+async fn tenant_metrics(
+    connection: &Connection,
+    tenant_id: &str,
+    params: &RefreshParams,
+) -> Result<TenantMetricsResult, String> {
+    let (start, end) = params.metrics_range.bounds(Utc::now());
+    let options = GetTenantMetricsOptions::new(tenant_id)
+        .with_start_time(start)
+        .with_end_time(end)
+        .with_granularity(params.granularity);
+    call(connection, |sdk| sdk.get_tenant_metrics(options))
+        .await
+        .map_err(|e| format!("Failed to get metrics: {}", e))
+}
+
+/// Fetch the last heartbeat of the first page of running instances.
+///
+/// Instance summaries carry no heartbeat, so every instance is looked up,
+/// [`SCAN_CONCURRENCY`] at a time; instances that finish or vanish
+/// meanwhile are left out.
+async fn scan_running(
+    connection: &Connection,
+    tenant_id: Option<String>,
+) -> Result<RunningScan, String> {
+    let options = ListInstancesOptions {
+        tenant_id,
+        status: Some(InstanceStatus::Running),
+        limit: PAGE_SIZE,
+        ..Default::default()
+    };
+    let running = call(connection, |sdk| sdk.list_instances(options))
+        .await
+        .map_err(|e| format!("Failed to list running instances: {}", e))?;
+
+    let ids: Vec<String> = running
+        .instances
+        .iter()
+        .map(|inst| inst.instance_id.clone())
+        .collect();
+    let details: Vec<_> = stream::iter(ids)
+        .map(|id| async move { call(connection, |sdk| sdk.get_instance_status(&id)).await })
+        .buffered(SCAN_CONCURRENCY)
+        .collect()
+        .await;
+    let instances = running
+        .instances
+        .into_iter()
+        .zip(details)
+        .filter_map(|(inst, info)| {
+            let info = info
+                .ok()
+                .filter(|info| info.status == InstanceStatus::Running)?;
+            let last_seen = info
+                .heartbeat_at
+                .or(info.started_at)
+                .unwrap_or(info.created_at);
+            Some((inst, last_seen))
+        })
+        .collect();
+
+    Ok(RunningScan {
+        instances,
+        total: running.total_count,
+    })
+}
+
+/// List options for a page of instances
+pub fn instances_options(
+    tenant_id: Option<String>,
+    status: Option<InstanceStatus>,
+    filter: &InstanceFilter,
+    limit: u32,
+    offset: u32,
+) -> ListInstancesOptions {
+    let mut options = ListInstancesOptions {
+        tenant_id,
+        status,
+        limit,
+        offset,
+        ..Default::default()
+    };
+    filter.apply(&mut options);
+    options
+}
+
+/// Run an SDK call on the shared connection
+pub async fn call<'a, T, F, Fut>(connection: &'a Connection, f: F) -> Result<T, SdkError>
+where
+    F: FnOnce(&'a ManagementSdk) -> Fut,
+    Fut: std::future::Future<Output = Result<T, SdkError>>,
+{
+    match connection.sdk().await {
+        Ok(sdk) => connection.call(f(sdk)).await,
+        Err(e) => Err(SdkError::Connection(e)),
+    }
+}